@@ -0,0 +1,59 @@
+//! Management CLI for `hook_common::acl`'s per-role permission files.
+//!
+//! Usage:
+//!   acl new <role>              create an empty ACL for <role>
+//!   acl add <role> <scope>      grant <scope> to <role>
+//!   acl rm <role> <scope>       revoke <scope> from <role>
+//!   acl ls                      list every role and its granted scopes
+//!
+//! Operates on `.claude/acl/` under `CLAUDE_PROJECT_DIR` (or the current
+//! directory if unset), the same location `hook_common::acl::Acl::load`
+//! reads from at hook run time.
+
+use anyhow::{bail, Context, Result};
+use hook_common::acl::{self, Acl};
+use std::path::PathBuf;
+
+fn main() -> Result<()> {
+    let dir = acl_dir();
+    let mut args = std::env::args().skip(1);
+    let command = args.next().context(USAGE)?;
+
+    match command.as_str() {
+        "new" => {
+            let role = args.next().context(USAGE)?;
+            acl::new_role(&dir, &role)?;
+            println!("created ACL for role '{}'", role);
+        }
+        "add" => {
+            let role = args.next().context(USAGE)?;
+            let scope = args.next().context(USAGE)?;
+            acl::add_scope(&dir, &role, &scope)?;
+            println!("granted '{}' to role '{}'", scope, role);
+        }
+        "rm" => {
+            let role = args.next().context(USAGE)?;
+            let scope = args.next().context(USAGE)?;
+            acl::remove_scope(&dir, &role, &scope)?;
+            println!("revoked '{}' from role '{}'", scope, role);
+        }
+        "ls" => {
+            for (role, scopes) in acl::list_roles(&dir)? {
+                println!("{}:", role);
+                for scope in scopes {
+                    println!("  {}", scope);
+                }
+            }
+        }
+        other => bail!("unknown command '{}'\n{}", other, USAGE),
+    }
+
+    Ok(())
+}
+
+const USAGE: &str = "usage: acl <new|add|rm|ls> [role] [scope]";
+
+fn acl_dir() -> PathBuf {
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+    Acl::default_dir(&PathBuf::from(project_dir))
+}
@@ -0,0 +1,172 @@
+//! Declarative per-role permission ACL.
+//!
+//! Hooks like `enforce-hierarchy` used to hardcode who may do what as
+//! `match (parent_role, target_role)` arms and literal integers, so
+//! changing the hierarchy meant recompiling. This mirrors Tauri's
+//! capability/permission model instead: each role gets a flat list of
+//! named capability strings ("scopes", e.g. `edit:config`,
+//! `spawn:musician`, `bash:git`) in `.claude/acl/<role>.toml`, editable
+//! without touching Rust. The `acl` binary (see `acl-cli`) manages these
+//! files; hooks just call [`Acl::load`] and [`Acl::grants`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct RoleAclFile {
+    #[serde(default)]
+    scopes: BTreeSet<String>,
+}
+
+/// A role's resolved capability set, loaded from its TOML file. A missing
+/// or unparsable file resolves to an empty (no-capability) ACL, so an
+/// unconfigured role fails closed rather than open.
+#[derive(Debug, Clone, Default)]
+pub struct Acl {
+    scopes: BTreeSet<String>,
+}
+
+impl Acl {
+    /// Default ACL directory: `<project_dir>/.claude/acl`.
+    pub fn default_dir(project_dir: &Path) -> PathBuf {
+        project_dir.join(".claude").join("acl")
+    }
+
+    /// Load `<dir>/<role>.toml`.
+    pub fn load(dir: &Path, role: &str) -> Self {
+        let scopes = std::fs::read_to_string(role_path(dir, role))
+            .ok()
+            .and_then(|contents| toml::from_str::<RoleAclFile>(&contents).ok())
+            .map(|file| file.scopes)
+            .unwrap_or_default();
+        Self { scopes }
+    }
+
+    /// Whether this role's ACL grants `scope` (exact match).
+    pub fn grants(&self, scope: &str) -> bool {
+        self.scopes.contains(scope)
+    }
+
+    /// Number of scopes granted -- surfaced by hooks that report how many
+    /// permissions a role was auto-granted.
+    pub fn scope_count(&self) -> usize {
+        self.scopes.len()
+    }
+
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.scopes.iter().map(|s| s.as_str())
+    }
+}
+
+fn role_path(dir: &Path, role: &str) -> PathBuf {
+    dir.join(format!("{role}.toml"))
+}
+
+/// Create an empty ACL file for `role` if one doesn't already exist.
+pub fn new_role(dir: &Path, role: &str) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let path = role_path(dir, role);
+    if path.exists() {
+        return Ok(());
+    }
+    std::fs::write(path, toml::to_string_pretty(&RoleAclFile::default())?)?;
+    Ok(())
+}
+
+/// Grant `scope` to `role`, creating the role's file if needed.
+pub fn add_scope(dir: &Path, role: &str, scope: &str) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let path = role_path(dir, role);
+    let mut file: RoleAclFile = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default();
+    file.scopes.insert(scope.to_string());
+    std::fs::write(path, toml::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// Revoke `scope` from `role`. A no-op if the role or scope doesn't exist.
+pub fn remove_scope(dir: &Path, role: &str, scope: &str) -> anyhow::Result<()> {
+    let path = role_path(dir, role);
+    let Some(contents) = std::fs::read_to_string(&path).ok() else {
+        return Ok(());
+    };
+    let mut file: RoleAclFile = toml::from_str(&contents).unwrap_or_default();
+    file.scopes.remove(scope);
+    std::fs::write(path, toml::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// List every role with an ACL file in `dir`, along with its scopes.
+pub fn list_roles(dir: &Path) -> anyhow::Result<Vec<(String, Vec<String>)>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut roles = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+            continue;
+        }
+        let Some(role) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        let acl = Acl::load(dir, &role);
+        roles.push((role, acl.scopes().map(|s| s.to_string()).collect()));
+    }
+    roles.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(roles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_role_grants_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let acl = Acl::load(dir.path(), "conductor");
+        assert!(!acl.grants("edit:direct"));
+        assert_eq!(acl.scope_count(), 0);
+    }
+
+    #[test]
+    fn test_add_and_load_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        add_scope(dir.path(), "conductor", "spawn:musician").unwrap();
+        let acl = Acl::load(dir.path(), "conductor");
+        assert!(acl.grants("spawn:musician"));
+        assert!(!acl.grants("edit:direct"));
+    }
+
+    #[test]
+    fn test_remove_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        add_scope(dir.path(), "conductor", "bash:git").unwrap();
+        remove_scope(dir.path(), "conductor", "bash:git").unwrap();
+        let acl = Acl::load(dir.path(), "conductor");
+        assert!(!acl.grants("bash:git"));
+    }
+
+    #[test]
+    fn test_new_role_creates_empty_file() {
+        let dir = tempfile::tempdir().unwrap();
+        new_role(dir.path(), "reviewer").unwrap();
+        assert!(dir.path().join("reviewer.toml").exists());
+        assert_eq!(Acl::load(dir.path(), "reviewer").scope_count(), 0);
+    }
+
+    #[test]
+    fn test_list_roles() {
+        let dir = tempfile::tempdir().unwrap();
+        add_scope(dir.path(), "conductor", "spawn:musician").unwrap();
+        add_scope(dir.path(), "musician", "edit:implementation").unwrap();
+        let roles = list_roles(dir.path()).unwrap();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(roles[0].0, "conductor");
+        assert_eq!(roles[1].0, "musician");
+    }
+}
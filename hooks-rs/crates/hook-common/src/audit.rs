@@ -0,0 +1,225 @@
+//! Blackbox audit log of hook decisions.
+//!
+//! Opt-in (via [`crate::output::HookOutput::write_stdout_logged`]) trail of
+//! every allow/ask/deny a hook emitted: one JSON line per decision,
+//! appended to a rotating file, analogous to a VCS blackbox command log.
+//! Lets users answer "why was my command denied" after the fact instead
+//! of only from live stderr.
+//!
+//! Plain entries (written via [`append_decision`]) only record the
+//! decision, not the `HookInput` that produced it, so they can't be
+//! replayed later. Hooks that expose a `run(&HookInput) -> Option<HookOutput>`
+//! library function (see `hook-replay`) can instead log via
+//! [`crate::output::HookOutput::write_stdout_logged_for_replay`], which
+//! also records `hook_name` and the original `tool_input` so the
+//! `hook-replay --log` mode can reconstruct the input and re-run the
+//! decision later.
+
+use crate::input::HookInput;
+use crate::output::{HookOutput, PermissionDecision};
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_LOG_PATH: &str = "/tmp/claude-hook-audit.jsonl";
+/// Past this size, the log is rotated to `<path>.1` (overwriting any
+/// previous `.1`) before the next line is appended.
+const ROTATE_THRESHOLD_BYTES: u64 = 5 * 1024 * 1024;
+/// Reason/context strings longer than this many characters are truncated
+/// to that many characters (plus a `...` marker) in the log.
+const MAX_FIELD_LEN: usize = 500;
+
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    timestamp: String,
+    hook_event_name: &'a str,
+    tool_name: &'a str,
+    permission_decision: Option<PermissionDecision>,
+    blocking_error: Option<String>,
+    reason: Option<String>,
+    /// Replay harness identifier (see `hook-replay`'s `hook_registry`);
+    /// `None` for entries logged via the plain [`append_decision`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hook_name: Option<&'a str>,
+    /// The original `tool_input`, so `hook-replay --log` can reconstruct
+    /// the `HookInput` this entry was decided from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_input: Option<serde_json::Value>,
+}
+
+/// Append one line recording `output`'s decision for `tool_name` to the
+/// blackbox log at [`log_path`]. Rotates the log first if it has grown
+/// past [`ROTATE_THRESHOLD_BYTES`].
+pub fn append_decision(output: &HookOutput, tool_name: &str) -> Result<()> {
+    append_decision_to(&log_path(), output, tool_name, ROTATE_THRESHOLD_BYTES)
+}
+
+/// Same as [`append_decision`], but also records `hook_name` and `input`'s
+/// `tool_input` so this entry can later be replayed by `hook-replay --log`.
+pub fn append_decision_for_replay(hook_name: &str, input: &HookInput, output: &HookOutput) -> Result<()> {
+    append_decision_for_replay_to(&log_path(), hook_name, input, output, ROTATE_THRESHOLD_BYTES)
+}
+
+/// Log file location, overridable via `CLAUDE_HOOK_AUDIT_LOG` for tests
+/// and non-default setups.
+fn log_path() -> PathBuf {
+    std::env::var("CLAUDE_HOOK_AUDIT_LOG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_LOG_PATH))
+}
+
+fn append_decision_to(
+    path: &Path,
+    output: &HookOutput,
+    tool_name: &str,
+    rotate_threshold: u64,
+) -> Result<()> {
+    rotate_if_large(path, rotate_threshold)?;
+
+    let specific = &output.hook_specific_output;
+    let entry = AuditEntry {
+        timestamp: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        hook_event_name: &specific.hook_event_name,
+        tool_name,
+        permission_decision: specific.permission_decision,
+        blocking_error: specific.blocking_error.as_deref().map(truncate),
+        reason: specific.additional_context.as_deref().map(truncate),
+        hook_name: None,
+        tool_input: None,
+    };
+
+    write_entry(path, &entry)
+}
+
+fn append_decision_for_replay_to(
+    path: &Path,
+    hook_name: &str,
+    input: &HookInput,
+    output: &HookOutput,
+    rotate_threshold: u64,
+) -> Result<()> {
+    rotate_if_large(path, rotate_threshold)?;
+
+    let specific = &output.hook_specific_output;
+    let entry = AuditEntry {
+        timestamp: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        hook_event_name: &specific.hook_event_name,
+        tool_name: &input.tool_name,
+        permission_decision: specific.permission_decision,
+        blocking_error: specific.blocking_error.as_deref().map(truncate),
+        reason: specific.additional_context.as_deref().map(truncate),
+        hook_name: Some(hook_name),
+        tool_input: Some(serde_json::to_value(&input.tool_input)?),
+    };
+
+    write_entry(path, &entry)
+}
+
+fn write_entry(path: &Path, entry: &AuditEntry) -> Result<()> {
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+fn truncate(s: &str) -> String {
+    if s.len() <= MAX_FIELD_LEN {
+        return s.to_string();
+    }
+    match s.char_indices().nth(MAX_FIELD_LEN) {
+        Some((byte_idx, _)) => format!("{}...", &s[..byte_idx]),
+        None => s.to_string(),
+    }
+}
+
+fn rotate_if_large(path: &Path, threshold: u64) -> Result<()> {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Ok(());
+    };
+    if metadata.len() < threshold {
+        return Ok(());
+    }
+    let rotated = PathBuf::from(format!("{}.1", path.display()));
+    std::fs::rename(path, rotated)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_append_decision_writes_jsonl_line() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let output = HookOutput::deny().with_context("blocked for safety");
+
+        append_decision_to(&path, &output, "Bash", ROTATE_THRESHOLD_BYTES).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\"tool_name\":\"Bash\""));
+        assert!(contents.contains("\"permission_decision\":\"deny\""));
+        assert!(contents.contains("blocked for safety"));
+    }
+
+    #[test]
+    fn test_append_decision_is_append_only() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let output = HookOutput::allow();
+
+        append_decision_to(&path, &output, "Edit", ROTATE_THRESHOLD_BYTES).unwrap();
+        append_decision_to(&path, &output, "Write", ROTATE_THRESHOLD_BYTES).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_rotation_renames_past_threshold() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        std::fs::write(&path, "x".repeat(100)).unwrap();
+
+        let output = HookOutput::allow();
+        append_decision_to(&path, &output, "Bash", 10).unwrap();
+
+        assert!(dir.path().join("audit.jsonl.1").exists());
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_append_decision_for_replay_records_hook_name_and_input() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let mut input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        input.tool_input.command = Some("gh pr merge 1".to_string());
+        let output = HookOutput::deny().with_context("blocked");
+
+        append_decision_for_replay_to(&path, "enforce-no-merge", &input, &output, ROTATE_THRESHOLD_BYTES)
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\"hook_name\":\"enforce-no-merge\""));
+        assert!(contents.contains("gh pr merge 1"));
+    }
+
+    #[test]
+    fn test_truncate_long_reason() {
+        let long = "a".repeat(1000);
+        let output = HookOutput::allow().with_context(long);
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+
+        append_decision_to(&path, &output, "Bash", ROTATE_THRESHOLD_BYTES).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("..."));
+        assert!(contents.len() < 1000);
+    }
+}
@@ -0,0 +1,260 @@
+//! Capability-based access control manifest for the delegation hook.
+//!
+//! `enforce-delegation` used to hardcode `WORK_TOOLS`, the conductor/
+//! musician roles, and an `is_allowed_path` allowlist as Rust constants.
+//! Borrowing Tauri's ACL model -- a *permission* grants a specific
+//! action/scope, and named *capabilities* bundle permissions and apply
+//! them to roles -- this resolves both the path allowlist short-circuit
+//! and "does this tool need delegation" from one declarative manifest
+//! instead of recompiling to change either.
+//!
+//! `.claude/acl/capabilities.toml`:
+//!
+//! ```toml
+//! [permissions.edit-claude-config]
+//! tool = "*"
+//! path_glob = ".claude/**"
+//! decision = "allow"
+//!
+//! [permissions.track-edit]
+//! tool = "Edit"
+//! decision = "deny"   # "deny" here means "not a free pass -- track it"
+//!
+//! [capabilities.conductor-default]
+//! permissions = ["edit-claude-config", "track-edit"]
+//! roles = ["conductor"]
+//! ```
+//!
+//! [`CapabilitySet::resolve`] looks up the most specific permission
+//! matching a `(tool_name, path)` pair (a permission with a `path_glob`
+//! beats one without; an exact `tool` beats `"*"`; `deny` wins a tie) and
+//! returns its decision. `Some(Allow)` means the caller should pass
+//! through without counting it as work; `Some(Deny)` means the tool
+//! counts toward the delegation threshold; `None` means this role has no
+//! opinion on the tool at all (e.g. `Task`, which isn't a work tool).
+
+use crate::paths::{pattern_matches, Decision};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Deserialize)]
+struct PermissionDef {
+    tool: String,
+    #[serde(default)]
+    path_glob: Option<String>,
+    decision: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct CapabilityDef {
+    #[serde(default)]
+    permissions: Vec<String>,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestFile {
+    #[serde(default)]
+    permissions: HashMap<String, PermissionDef>,
+    #[serde(default)]
+    capabilities: HashMap<String, CapabilityDef>,
+}
+
+#[derive(Debug, Clone)]
+struct ResolvedPermission {
+    tool: String,
+    path_glob: Option<String>,
+    decision: Decision,
+}
+
+/// A role's effective, flattened set of permissions, resolved from every
+/// capability in the manifest that applies to that role.
+pub struct CapabilitySet {
+    permissions: Vec<ResolvedPermission>,
+}
+
+impl CapabilitySet {
+    /// Default manifest path: `<project_dir>/.claude/acl/capabilities.toml`.
+    pub fn default_path(project_dir: &Path) -> PathBuf {
+        project_dir.join(".claude").join("acl").join("capabilities.toml")
+    }
+
+    /// Load the manifest at `path` and flatten every capability assigned
+    /// to `role` (or to `"*"`, any role) into its resolved permission
+    /// list. A missing or unparsable manifest falls back to
+    /// [`default_manifest`], which reproduces `enforce-delegation`'s old
+    /// hardcoded behavior.
+    pub fn load(path: &Path, role: &str) -> Self {
+        let file = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str::<ManifestFile>(&contents).ok())
+            .filter(|file: &ManifestFile| !file.capabilities.is_empty())
+            .unwrap_or_else(default_manifest);
+
+        let mut permissions = Vec::new();
+        for capability in file.capabilities.values() {
+            if !capability.roles.iter().any(|r| r == role || r == "*") {
+                continue;
+            }
+            for name in &capability.permissions {
+                if let Some(def) = file.permissions.get(name) {
+                    permissions.push(ResolvedPermission {
+                        tool: def.tool.clone(),
+                        path_glob: def.path_glob.clone(),
+                        decision: match def.decision.as_str() {
+                            "allow" => Decision::Allow,
+                            _ => Decision::Deny,
+                        },
+                    });
+                }
+            }
+        }
+
+        Self { permissions }
+    }
+
+    /// Resolve the most specific permission matching `tool_name` (and
+    /// `path`, if this role has any path-scoped permissions for that
+    /// tool), returning its decision. `None` means no permission in this
+    /// role's effective set mentions `tool_name` at all.
+    pub fn resolve(&self, tool_name: &str, path: Option<&str>) -> Option<Decision> {
+        let mut best: Option<(u32, Decision)> = None;
+
+        for permission in &self.permissions {
+            if permission.tool != "*" && permission.tool != tool_name {
+                continue;
+            }
+
+            let specificity = match &permission.path_glob {
+                None => 0,
+                Some(glob) => {
+                    let Some(path) = path else { continue };
+                    if !pattern_matches(glob, path) {
+                        continue;
+                    }
+                    glob.split('/').filter(|c| !c.is_empty()).count() as u32 + 1
+                }
+            };
+            let tool_specificity = if permission.tool == "*" { 0 } else { 1 };
+            let rank = specificity * 2 + tool_specificity;
+
+            match &best {
+                None => best = Some((rank, permission.decision)),
+                Some((best_rank, best_decision)) => {
+                    if rank > *best_rank || (rank == *best_rank && permission.decision == Decision::Deny && *best_decision == Decision::Allow)
+                    {
+                        best = Some((rank, permission.decision));
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, decision)| decision)
+    }
+}
+
+/// Reproduces the hardcoded behavior `enforce-delegation` had before this
+/// manifest existed: Conductor may freely touch `.claude/`, `memory/`,
+/// and a few config files, and must track every other use of the classic
+/// `WORK_TOOLS` set; Musician is unrestricted (no permissions at all, so
+/// [`CapabilitySet::resolve`] always returns `None` for it).
+fn default_manifest() -> ManifestFile {
+    let mut permissions = HashMap::new();
+    for (name, glob) in [
+        ("allow-claude-config", ".claude/**"),
+        ("allow-memory", "memory/**"),
+        ("allow-pyproject", "pyproject.toml"),
+        ("allow-settings", "settings.json"),
+        ("allow-gitignore", ".gitignore"),
+    ] {
+        permissions.insert(
+            name.to_string(),
+            PermissionDef { tool: "*".to_string(), path_glob: Some(glob.to_string()), decision: "allow".to_string() },
+        );
+    }
+    for tool in ["Edit", "Write", "Read", "Bash", "WebFetch", "WebSearch"] {
+        permissions.insert(
+            format!("track-{}", tool.to_lowercase()),
+            PermissionDef { tool: tool.to_string(), path_glob: None, decision: "deny".to_string() },
+        );
+    }
+
+    let mut capabilities = HashMap::new();
+    capabilities.insert(
+        "conductor-default".to_string(),
+        CapabilityDef { permissions: permissions.keys().cloned().collect(), roles: vec!["conductor".to_string()] },
+    );
+
+    ManifestFile { permissions, capabilities }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_manifest_allows_claude_dir() {
+        let set = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "conductor");
+        assert_eq!(set.resolve("Edit", Some(".claude/rules/test.md")), Some(Decision::Allow));
+    }
+
+    #[test]
+    fn test_default_manifest_tracks_edit_outside_allowlist() {
+        let set = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "conductor");
+        assert_eq!(set.resolve("Edit", Some("src/main.rs")), Some(Decision::Deny));
+    }
+
+    #[test]
+    fn test_musician_has_no_opinion() {
+        let set = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "musician");
+        assert_eq!(set.resolve("Edit", Some("src/main.rs")), None);
+        assert_eq!(set.resolve("Bash", None), None);
+    }
+
+    #[test]
+    fn test_task_tool_is_untracked() {
+        let set = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "conductor");
+        assert_eq!(set.resolve("Task", None), None);
+    }
+
+    #[test]
+    fn test_resolve_normalizes_the_path_like_path_engine_does() {
+        // Shares `paths::pattern_matches`, so a `..`-relative path resolves
+        // the same way it would under `PathEngine::check`.
+        let set = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "conductor");
+        assert_eq!(set.resolve("Edit", Some("src/../.claude/rules/test.md")), Some(Decision::Allow));
+    }
+
+    #[test]
+    fn test_custom_manifest_overrides_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capabilities.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [permissions.edit-docs]
+            tool = "Edit"
+            path_glob = "docs/**"
+            decision = "allow"
+
+            [permissions.track-edit]
+            tool = "Edit"
+            decision = "deny"
+
+            [capabilities.reviewer-base]
+            permissions = ["edit-docs", "track-edit"]
+            roles = ["reviewer"]
+            "#,
+        )
+        .unwrap();
+
+        let set = CapabilitySet::load(&path, "reviewer");
+        assert_eq!(set.resolve("Edit", Some("docs/guide.md")), Some(Decision::Allow));
+        assert_eq!(set.resolve("Edit", Some("src/main.rs")), Some(Decision::Deny));
+
+        let other_role = CapabilitySet::load(&path, "conductor");
+        assert_eq!(other_role.resolve("Edit", Some("docs/guide.md")), None);
+    }
+}
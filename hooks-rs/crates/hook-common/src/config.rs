@@ -0,0 +1,133 @@
+//! Layered config for tunable hook policy, loaded from `~/.config/ai/hooks.toml`.
+//!
+//! Mirrors the "use config if available, else built-in defaults" fallback
+//! used by mature VCS CLIs: every setting is optional, and a missing or
+//! unparsable config file silently falls back to the hook's compiled-in
+//! defaults, so operators can retune policy (recall counts, review
+//! thresholds, tool eligibility, extra block patterns) without a rebuild,
+//! while hooks keep working unconfigured.
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HooksConfig {
+    #[serde(default)]
+    pub recall: RecallConfig,
+    #[serde(default)]
+    pub record: RecordConfig,
+    #[serde(default)]
+    pub review: ReviewConfig,
+    #[serde(default)]
+    pub no_merge: NoMergeConfig,
+}
+
+/// Overrides for the memory-recall hook (`pre-tool-recall`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecallConfig {
+    /// Replaces the built-in `RECALL_TOOLS` list.
+    pub tools: Option<Vec<String>>,
+    /// Replaces the built-in `MAX_RECALL` cap.
+    pub max_recall: Option<usize>,
+}
+
+/// Overrides for the memory-record hook (`post-tool-record`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecordConfig {
+    /// Replaces the built-in `RECORDABLE_TOOLS` list.
+    pub recordable_tools: Option<Vec<String>>,
+    /// Replaces the built-in `SKIP_TOOLS` list.
+    pub skip_tools: Option<Vec<String>>,
+    /// Appended to the built-in failure indicators checked by `determine_success`.
+    #[serde(default)]
+    pub extra_failure_indicators: Vec<String>,
+}
+
+/// Overrides for the review-suggestion hook (`post-implementation-review`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReviewConfig {
+    /// Replaces the built-in `MIN_FILES_FOR_REVIEW` threshold.
+    pub min_files_for_review: Option<usize>,
+    /// Replaces the built-in `MIN_LINES_FOR_REVIEW` threshold.
+    pub min_lines_for_review: Option<usize>,
+}
+
+/// Overrides for the no-merge hook (`enforce-no-merge`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NoMergeConfig {
+    /// Extra regex patterns checked in addition to the built-in merge patterns.
+    #[serde(default)]
+    pub extra_block_patterns: Vec<String>,
+}
+
+impl HooksConfig {
+    /// Load config from `~/.config/ai/hooks.toml` (or `AI_HOOKS_CONFIG` if
+    /// set), falling back to all-default (i.e. "use built-in behavior") when
+    /// the file is missing or fails to parse.
+    pub fn load() -> Self {
+        Self::load_from(&Self::default_path())
+    }
+
+    fn default_path() -> Option<PathBuf> {
+        if let Ok(custom_path) = std::env::var("AI_HOOKS_CONFIG") {
+            return Some(PathBuf::from(custom_path));
+        }
+        dirs::config_dir().map(|dir| dir.join("ai").join("hooks.toml"))
+    }
+
+    fn load_from(path: &Option<PathBuf>) -> Self {
+        path.as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_from_missing_path_is_default() {
+        let config = HooksConfig::load_from(&Some(PathBuf::from("/nonexistent/hooks.toml")));
+        assert!(config.recall.tools.is_none());
+        assert!(config.recall.max_recall.is_none());
+    }
+
+    #[test]
+    fn test_load_from_reads_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hooks.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [recall]
+            tools = ["Bash"]
+            max_recall = 3
+
+            [review]
+            min_files_for_review = 10
+
+            [no_merge]
+            extra_block_patterns = ["\\bsvn\\s+merge\\b"]
+            "#,
+        )
+        .unwrap();
+
+        let config = HooksConfig::load_from(&Some(path));
+        assert_eq!(config.recall.tools, Some(vec!["Bash".to_string()]));
+        assert_eq!(config.recall.max_recall, Some(3));
+        assert_eq!(config.review.min_files_for_review, Some(10));
+        assert_eq!(config.no_merge.extra_block_patterns, vec!["\\bsvn\\s+merge\\b".to_string()]);
+    }
+
+    #[test]
+    fn test_load_from_unparsable_falls_back_to_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hooks.toml");
+        std::fs::write(&path, "not valid toml [[[").unwrap();
+
+        let config = HooksConfig::load_from(&Some(path));
+        assert!(config.recall.tools.is_none());
+    }
+}
@@ -0,0 +1,163 @@
+//! Render a unified diff between two git refs, suitable for embedding in a
+//! PR body.
+//!
+//! Used by the auto-create-pr hook so draft PRs open with an actual
+//! summary of what changed instead of a fixed placeholder string, and
+//! reusable by any other hook that wants to embed the same formatted diff.
+
+use crate::subprocess::run_command_with_timeout;
+use anyhow::{Context, Result};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default cap on the rendered diff body, in bytes. Large diffs get
+/// truncated at a file boundary with a footer noting how much was cut.
+const DEFAULT_BYTE_BUDGET: usize = 8_000;
+
+/// Render a unified diff of `base...head` under `project_dir`, prefixed
+/// with a short stat summary (files changed, insertions, deletions) and
+/// capped at `DEFAULT_BYTE_BUDGET` bytes. See [`render_unified_with_budget`]
+/// to override the budget.
+pub fn render_unified(project_dir: &str, base: &str, head: &str) -> Result<String> {
+    render_unified_with_budget(project_dir, base, head, DEFAULT_BYTE_BUDGET)
+}
+
+/// Same as [`render_unified`] but with a configurable byte budget for the
+/// rendered body.
+pub fn render_unified_with_budget(
+    project_dir: &str,
+    base: &str,
+    head: &str,
+    byte_budget: usize,
+) -> Result<String> {
+    let result = run_command_with_timeout(
+        &format!(
+            "cd {} && git diff --unified=3 {}...{}",
+            project_dir, base, head
+        ),
+        TIMEOUT,
+    )
+    .with_context(|| format!("Failed to diff {}...{}", base, head))?;
+
+    if !result.success {
+        anyhow::bail!("git diff failed: {}", result.stderr);
+    }
+
+    Ok(render_diff_text(&result.stdout, byte_budget))
+}
+
+/// Split raw `git diff` output into per-file hunks, compute the stat
+/// summary, and re-render with a stat header, truncating at `byte_budget`
+/// on a file boundary.
+fn render_diff_text(diff: &str, byte_budget: usize) -> String {
+    let files = split_into_file_diffs(diff);
+
+    let mut insertions = 0usize;
+    let mut deletions = 0usize;
+    for file in &files {
+        for line in file.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                continue;
+            }
+            if line.starts_with('+') {
+                insertions += 1;
+            } else if line.starts_with('-') {
+                deletions += 1;
+            }
+        }
+    }
+
+    let stat_line = format!(
+        "{} file{} changed, {} insertion{}(+), {} deletion{}(-)\n\n",
+        files.len(),
+        if files.len() == 1 { "" } else { "s" },
+        insertions,
+        if insertions == 1 { "" } else { "s" },
+        deletions,
+        if deletions == 1 { "" } else { "s" },
+    );
+
+    let mut body = stat_line;
+    let mut included = 0;
+    for file in &files {
+        if body.len() + file.len() > byte_budget {
+            break;
+        }
+        body.push_str(file);
+        included += 1;
+    }
+
+    let remaining = files.len() - included;
+    if remaining > 0 {
+        body.push_str(&format!("\n… {} more file(s) truncated\n", remaining));
+    }
+
+    body
+}
+
+/// Split raw unified diff text into one chunk per `diff --git` section,
+/// each chunk retaining its leading `diff --git` line.
+fn split_into_file_diffs(diff: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut current = String::new();
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git") && !current.is_empty() {
+            files.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.is_empty() {
+        files.push(current);
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_DIFF: &str = "\
+diff --git a/a.rs b/a.rs
+index 000..111 100644
+--- a/a.rs
++++ b/a.rs
+@@ -1,2 +1,2 @@
+-old line
++new line
+ context line
+diff --git a/b.rs b/b.rs
+index 000..111 100644
+--- a/b.rs
++++ b/b.rs
+@@ -1,1 +1,2 @@
+ context
++added line
+";
+
+    #[test]
+    fn test_splits_into_two_files() {
+        let files = split_into_file_diffs(SAMPLE_DIFF);
+        assert_eq!(files.len(), 2);
+        assert!(files[0].starts_with("diff --git a/a.rs"));
+        assert!(files[1].starts_with("diff --git a/b.rs"));
+    }
+
+    #[test]
+    fn test_render_includes_stat_summary() {
+        let rendered = render_diff_text(SAMPLE_DIFF, DEFAULT_BYTE_BUDGET);
+        assert!(rendered.starts_with("2 files changed, 2 insertions(+), 1 deletion(-)"));
+        assert!(rendered.contains("+new line"));
+        assert!(rendered.contains("+added line"));
+    }
+
+    #[test]
+    fn test_render_truncates_at_byte_budget() {
+        let rendered = render_diff_text(SAMPLE_DIFF, 1);
+        assert!(rendered.contains("more file(s) truncated"));
+        assert!(!rendered.contains("diff --git"));
+    }
+}
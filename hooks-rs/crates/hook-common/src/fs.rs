@@ -0,0 +1,132 @@
+//! Filesystem abstraction for hooks.
+//!
+//! Marker/state-flag helpers (`is_marker_valid`, `write_marker`,
+//! `create_conductor_marker`, the memory-loaded flag, ...) write directly
+//! to paths like `/tmp/...` or `.claude/.session-pr-created`, which makes
+//! them impossible to exercise deterministically in tests and prone to
+//! cross-session flag collisions in CI. Hooks that need marker/state files
+//! should take `&dyn Fs` instead of calling `std::fs` directly, use
+//! [`RealFs`] in `main`, and [`FakeFs`] in tests.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Filesystem operations hooks need for marker/state files.
+pub trait Fs {
+    fn read_to_string(&self, path: &Path) -> Result<String>;
+    fn write(&self, path: &Path, contents: &str) -> Result<()>;
+    fn create_dir_all(&self, path: &Path) -> Result<()>;
+    fn remove_file(&self, path: &Path) -> Result<()>;
+    fn exists(&self, path: &Path) -> bool;
+}
+
+/// Production implementation, backed by `std::fs`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFs;
+
+impl Fs for RealFs {
+    fn read_to_string(&self, path: &Path) -> Result<String> {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> Result<()> {
+        std::fs::write(path, contents)
+            .with_context(|| format!("Failed to write file: {}", path.display()))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> Result<()> {
+        std::fs::create_dir_all(path)
+            .with_context(|| format!("Failed to create directory: {}", path.display()))
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove file: {}", path.display()))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+/// In-memory filesystem for tests. Paths are keys into a map guarded by a
+/// mutex, so `&self` methods can mutate shared state the way real syscalls
+/// would without requiring `&mut self` plumbing through hook code.
+#[derive(Debug, Default)]
+pub struct FakeFs {
+    files: Mutex<HashMap<PathBuf, String>>,
+}
+
+impl FakeFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Fs for FakeFs {
+    fn read_to_string(&self, path: &Path) -> Result<String> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .with_context(|| format!("FakeFs: no such file: {}", path.display()))
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> Result<()> {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), contents.to_string());
+        Ok(())
+    }
+
+    fn create_dir_all(&self, _path: &Path) -> Result<()> {
+        // FakeFs models files by full path, not a directory tree.
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        self.files.lock().unwrap().remove(path);
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.lock().unwrap().contains_key(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fake_fs_write_then_read() {
+        let fs = FakeFs::new();
+        let path = Path::new("/tmp/marker");
+
+        assert!(!fs.exists(path));
+        fs.write(path, "hello").unwrap();
+        assert!(fs.exists(path));
+        assert_eq!(fs.read_to_string(path).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_fake_fs_remove_file() {
+        let fs = FakeFs::new();
+        let path = Path::new("/tmp/marker");
+
+        fs.write(path, "hello").unwrap();
+        fs.remove_file(path).unwrap();
+        assert!(!fs.exists(path));
+    }
+
+    #[test]
+    fn test_fake_fs_read_missing_file_errors() {
+        let fs = FakeFs::new();
+        assert!(fs.read_to_string(Path::new("/tmp/missing")).is_err());
+    }
+}
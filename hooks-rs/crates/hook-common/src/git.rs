@@ -0,0 +1,159 @@
+//! In-process git operations via `gix`.
+//!
+//! Hooks that drive git through shelled-out `git` strings pay for a process
+//! spawn (and a 30s timeout guard) per call, and are brittle to quoting.
+//! This module exposes typed operations for the hot paths hooks actually
+//! need. Reading the current hash and creating/listing branches run
+//! in-process against an open `gix::Repository` and return structured
+//! errors instead of parsed CLI stdout.
+//!
+//! `checkout`, `commit_all`, `push`, `fetch` and `delete_branch` shell out
+//! to the `git` CLI internally behind the same typed signatures: they need
+//! worktree updates or network access rather than only the ref graph.
+//! PR creation/listing is unrelated to
+//! this module and still goes through `gh` (see `subprocess::gh`).
+
+use crate::subprocess::{git as git_cli, run_command_with_timeout};
+use anyhow::{Context, Result};
+use gix::bstr::ByteSlice;
+use gix::Repository;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Open the repository rooted at (or above) `path`.
+pub fn open(path: &str) -> Result<Repository> {
+    gix::discover(path).with_context(|| format!("Failed to open git repo at {}", path))
+}
+
+/// Short hash (7 hex chars) of the current HEAD commit.
+pub fn current_short_hash(repo: &Repository) -> Result<String> {
+    let commit = repo.head_commit().context("Failed to resolve HEAD commit")?;
+    Ok(commit.id().to_hex_with_len(7).to_string())
+}
+
+/// List local branch names (short form, e.g. `main`, not `refs/heads/main`).
+pub fn list_local_branches(repo: &Repository) -> Result<Vec<String>> {
+    let platform = repo.references().context("Failed to read refs")?;
+    let mut branches = Vec::new();
+
+    for reference in platform
+        .local_branches()
+        .context("Failed to list local branches")?
+    {
+        let reference = reference.map_err(|e| anyhow::anyhow!(e)).context("Failed to read local branch ref")?;
+        if let Ok(name) = reference.name().shorten().to_str() {
+            branches.push(name.to_string());
+        }
+    }
+
+    Ok(branches)
+}
+
+/// Create a new branch named `branch_name` pointing at `from` (e.g.
+/// `"main"` or `"HEAD"`), without checking it out.
+pub fn create_branch_from(repo: &Repository, branch_name: &str, from: &str) -> Result<()> {
+    let target = repo
+        .rev_parse_single(from)
+        .with_context(|| format!("Failed to resolve ref: {}", from))?;
+
+    repo.reference(
+        format!("refs/heads/{}", branch_name),
+        target.detach(),
+        gix::refs::transaction::PreviousValue::MustNotExist,
+        format!("branch: created from {}", from),
+    )
+    .with_context(|| format!("Failed to create branch: {}", branch_name))?;
+
+    Ok(())
+}
+
+/// Check out a local branch, updating HEAD and the worktree.
+pub fn checkout(project_dir: &str, branch_name: &str) -> Result<()> {
+    // Worktree checkout (updating tracked files, not just HEAD) isn't yet
+    // exposed by a stable gix API the way ref reads are, so this goes
+    // through the CLI; everything that only needs the ref graph (hash,
+    // branch listing, branch creation) stays native above.
+    let result = run_command_with_timeout(
+        &format!("cd {} && git checkout {}", project_dir, branch_name),
+        TIMEOUT,
+    )?;
+    if !result.success {
+        anyhow::bail!("Failed to checkout {}: {}", branch_name, result.stderr);
+    }
+    Ok(())
+}
+
+/// Stage all changes and create a commit with `message`.
+pub fn commit_all(project_dir: &str, message: &str) -> Result<()> {
+    let add = run_command_with_timeout(&format!("cd {} && git add -A", project_dir), TIMEOUT)?;
+    if !add.success {
+        anyhow::bail!("Failed to stage changes: {}", add.stderr);
+    }
+
+    let escaped = message.replace('"', "\\\"");
+    let commit = run_command_with_timeout(
+        &format!("cd {} && git commit -m \"{}\"", project_dir, escaped),
+        TIMEOUT,
+    )?;
+    if !commit.success {
+        anyhow::bail!("Failed to commit: {}", commit.stderr);
+    }
+    Ok(())
+}
+
+/// Push `branch_name` to `remote`, setting it as the upstream.
+pub fn push(project_dir: &str, remote: &str, branch_name: &str) -> Result<()> {
+    let result = run_command_with_timeout(
+        &format!(
+            "cd {} && git push -u {} {}",
+            project_dir, remote, branch_name
+        ),
+        TIMEOUT,
+    )?;
+    if !result.success {
+        anyhow::bail!("Failed to push {}: {}", branch_name, result.stderr);
+    }
+    Ok(())
+}
+
+/// Fetch `remote` (all refs, or a single `branch_name` when given).
+pub fn fetch(project_dir: &str, remote: &str, branch_name: Option<&str>) -> Result<()> {
+    let cmd = match branch_name {
+        Some(branch) => format!("cd {} && git fetch {} {}", project_dir, remote, branch),
+        None => format!("cd {} && git fetch {}", project_dir, remote),
+    };
+    let result = run_command_with_timeout(&cmd, TIMEOUT)?;
+    if !result.success {
+        anyhow::bail!("Failed to fetch {}: {}", remote, result.stderr);
+    }
+    Ok(())
+}
+
+/// Delete a local branch. Mirrors `git branch -D`.
+pub fn delete_branch(project_dir: &str, branch_name: &str) -> Result<()> {
+    let result = git_cli(&format!("-C {} branch -D {}", project_dir, branch_name))?;
+    if !result.success {
+        anyhow::bail!("Failed to delete branch {}: {}", branch_name, result.stderr);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_current_repo() {
+        // This test assumes it runs inside a git repository checkout.
+        let repo = open(".").expect("should discover the enclosing repo");
+        assert!(current_short_hash(&repo).is_ok());
+    }
+
+    #[test]
+    fn test_list_local_branches_is_nonempty() {
+        let repo = open(".").expect("should discover the enclosing repo");
+        let branches = list_local_branches(&repo).expect("should list branches");
+        assert!(!branches.is_empty());
+    }
+}
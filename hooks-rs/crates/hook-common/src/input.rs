@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use std::io::{self, Read};
 
 /// Main hook input structure received from Claude Code.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HookInput {
     /// The name of the tool being called (e.g., "Bash", "Edit", "Write")
     #[serde(default)]
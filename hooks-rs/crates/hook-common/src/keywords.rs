@@ -0,0 +1,146 @@
+//! Config-driven keyword taxonomies for the suggestion hooks.
+//!
+//! `check-codex-after-plan` used to hardcode its trigger words as a
+//! `const` slice, so a team couldn't add a domain term or suppress a noisy
+//! trigger without rebuilding the binary; it now loads the `plan` group
+//! from here. (`agent-router` keeps its own trigger table in
+//! `.claude/config/router-triggers.toml` and does not read this file.) Modeled
+//! on Cargo's `aliased_command`, which resolves a command name through a
+//! user config before falling back to the built-in table, a
+//! [`KeywordGroup`] resolves its terms the same way: start from the
+//! hook's compiled-in defaults, drop anything listed under `remove`, add
+//! anything under `add`, and pull in any named `aliases` (other groups
+//! in the same file) as well. Everything lives in one TOML file,
+//! `.claude/config/suggestions.toml`:
+//!
+//! ```toml
+//! [plan]
+//! add = ["roadmap", "migration plan"]
+//! remove = ["structure"]
+//!
+//! [my-team-plan]
+//! aliases = ["plan"]
+//! add = ["rfc"]
+//! ```
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct GroupOverride {
+    #[serde(default)]
+    add: Vec<String>,
+    #[serde(default)]
+    remove: Vec<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct KeywordsFile {
+    #[serde(flatten)]
+    groups: HashMap<String, GroupOverride>,
+}
+
+/// A named set of trigger terms, resolved from built-in defaults plus any
+/// project overrides.
+#[derive(Debug, Clone)]
+pub struct KeywordGroup {
+    name: String,
+    terms: Vec<String>,
+}
+
+impl KeywordGroup {
+    /// Default config file: `<project_dir>/.claude/config/suggestions.toml`.
+    pub fn default_path(project_dir: &Path) -> PathBuf {
+        project_dir.join(".claude").join("config").join("suggestions.toml")
+    }
+
+    /// Resolve group `name`'s terms: `defaults`, minus anything in that
+    /// group's `remove` list, plus any aliased groups' `add` lists, plus
+    /// that group's own `add` list. A missing or unparsable config file
+    /// falls back to `defaults` unchanged.
+    pub fn load(path: &Path, name: &str, defaults: &[&str]) -> Self {
+        let file = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str::<KeywordsFile>(&contents).ok())
+            .unwrap_or_default();
+
+        let config = file.groups.get(name).cloned().unwrap_or_default();
+
+        let mut terms: Vec<String> = defaults
+            .iter()
+            .map(|s| s.to_string())
+            .filter(|term| !config.remove.contains(term))
+            .collect();
+
+        for alias in &config.aliases {
+            if let Some(aliased) = file.groups.get(alias) {
+                terms.extend(aliased.add.iter().cloned());
+            }
+        }
+        terms.extend(config.add.iter().cloned());
+
+        Self { name: name.to_string(), terms }
+    }
+
+    /// This group's name, for flowing into a suggestion message.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The first term (case-insensitively) contained in `haystack`, if any.
+    pub fn first_match(&self, haystack: &str) -> Option<&str> {
+        let haystack_lower = haystack.to_lowercase();
+        self.terms.iter().find(|term| haystack_lower.contains(term.to_lowercase().as_str())).map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let group = KeywordGroup::load(Path::new("/nonexistent/suggestions.toml"), "plan", &["design", "plan"]);
+        assert_eq!(group.first_match("let's design this"), Some("design"));
+    }
+
+    #[test]
+    fn test_add_extends_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suggestions.toml");
+        std::fs::write(&path, "[plan]\nadd = [\"segfault\"]\n").unwrap();
+
+        let group = KeywordGroup::load(&path, "plan", &["design"]);
+        assert_eq!(group.first_match("investigate the segfault"), Some("segfault"));
+    }
+
+    #[test]
+    fn test_remove_suppresses_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suggestions.toml");
+        std::fs::write(&path, "[plan]\nremove = [\"design\"]\n").unwrap();
+
+        let group = KeywordGroup::load(&path, "plan", &["design", "plan"]);
+        assert_eq!(group.first_match("let's design this"), None);
+        assert_eq!(group.first_match("make a plan"), Some("plan"));
+    }
+
+    #[test]
+    fn test_aliases_pull_in_another_groups_additions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suggestions.toml");
+        std::fs::write(
+            &path,
+            "[plan]\nadd = [\"segfault\"]\n\n[team-plan]\naliases = [\"plan\"]\nadd = [\"flaky test\"]\n",
+        )
+        .unwrap();
+
+        let group = KeywordGroup::load(&path, "team-plan", &["design"]);
+        assert_eq!(group.first_match("investigate the segfault"), Some("segfault"));
+        assert_eq!(group.first_match("fix the flaky test"), Some("flaky test"));
+        assert_eq!(group.name(), "team-plan");
+    }
+}
@@ -3,25 +3,74 @@
 //! This crate provides shared functionality for all Rust-based hooks:
 //! - JSON input/output parsing
 //! - Subprocess execution
+//! - In-process git operations (`git` module, via `gix`)
+//! - Filesystem abstraction for marker/state files (`fs` module)
+//! - Unified diff rendering for PR bodies (`diff` module)
+//! - Blackbox audit log of hook decisions (`audit` module)
+//! - Layered policy config with built-in fallback (`config` module)
+//! - Per-tool handler registry for recall/record extensibility (`tools` module)
+//! - External analyzer plugin pipeline over JSON-RPC (`plugins` module)
+//! - Shared session/role detection (`session` module)
+//! - Declarative per-role permission ACL (`acl` module)
+//! - Trie-based per-role file-path allow/deny engine (`paths` module)
+//! - Capability-based tool+path permission manifest (`capabilities` module)
+//! - In-process rule-plugin chain loaded from TOML manifests (`rules` module)
+//! - Cached VCS state shared across hooks in one session (`vcs_cache` module)
+//! - Config-driven keyword taxonomies for suggestion hooks (`keywords` module)
 //! - State file management
 //! - Error handling
 
+pub mod acl;
+pub mod audit;
+pub mod capabilities;
+pub mod config;
+pub mod diff;
+pub mod fs;
+pub mod git;
 pub mod input;
+pub mod keywords;
 pub mod output;
+pub mod paths;
+pub mod plugins;
+pub mod rules;
+pub mod session;
 pub mod state;
 pub mod subprocess;
+pub mod tools;
+pub mod vcs_cache;
 
+pub use acl::Acl;
+pub use config::HooksConfig;
+pub use fs::{Fs, RealFs};
 pub use input::{HookInput, ToolInput};
+pub use keywords::KeywordGroup;
 pub use output::{HookOutput, PermissionDecision};
+pub use paths::{Decision as PathDecision, PathEngine};
+pub use plugins::PluginRegistry;
+pub use rules::{HookRule, RuleChain};
+pub use session::{detect_session_kind, SessionKind};
 pub use state::StateManager;
 pub use subprocess::run_command;
+pub use tools::{ToolHandler, ToolHandlerRegistry};
+pub use vcs_cache::{has_any_open_pr, has_open_pr, has_uncommitted_changes};
 
 /// Re-export commonly used types
 pub mod prelude {
+    pub use crate::acl::Acl;
+    pub use crate::capabilities::CapabilitySet;
+    pub use crate::config::HooksConfig;
+    pub use crate::fs::{Fs, RealFs};
     pub use crate::input::{HookInput, ToolInput};
+    pub use crate::keywords::KeywordGroup;
     pub use crate::output::{HookOutput, PermissionDecision};
+    pub use crate::paths::{Decision as PathDecision, PathEngine};
+    pub use crate::plugins::PluginRegistry;
+    pub use crate::rules::{HookRule, RuleChain};
+    pub use crate::session::{detect_session_kind, SessionKind};
     pub use crate::state::StateManager;
     pub use crate::subprocess::run_command;
+    pub use crate::tools::{ToolHandler, ToolHandlerRegistry};
+    pub use crate::vcs_cache::{has_any_open_pr, has_open_pr, has_uncommitted_changes};
     pub use anyhow::{Context, Result};
     pub use serde::{Deserialize, Serialize};
 }
@@ -0,0 +1,340 @@
+//! Trie-based per-role file-path allow/deny engine.
+//!
+//! `is_allowed_file` used to do ad-hoc path-component scanning plus a fixed
+//! set of filename literals, which can't express per-role rules (Conductor
+//! may touch `.claude/`, but a future "reviewer" role might only touch
+//! `docs/`) and is hard to extend without recompiling. This compiles each
+//! role's allow/deny patterns into a prefix trie keyed on path components
+//! (mirroring monorail's approach to ownership-file matching), then
+//! resolves a candidate path by walking components and taking the longest
+//! (most specific) matching rule. An explicit deny wins over an allow at
+//! equal specificity.
+//!
+//! Patterns are `/`-separated path-component sequences: a literal
+//! component must match exactly, `*` matches exactly one component, and a
+//! trailing `**` matches any number of remaining components (including
+//! zero).
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// The outcome of matching a path against a role's rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RolePatterns {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PathAclFile {
+    #[serde(flatten)]
+    roles: HashMap<String, RolePatterns>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: HashMap<String, TrieNode>,
+    rule: Option<(Decision, String)>,
+}
+
+impl TrieNode {
+    fn insert(&mut self, components: &[&str], decision: Decision, pattern: &str) {
+        match components.split_first() {
+            None => self.rule = Some((decision, pattern.to_string())),
+            Some((&"**", _rest)) => {
+                self.children.entry("**".to_string()).or_default().rule =
+                    Some((decision, pattern.to_string()));
+            }
+            Some((first, rest)) => {
+                self.children.entry(first.to_string()).or_default().insert(rest, decision, pattern);
+            }
+        }
+    }
+}
+
+/// Result of [`PathEngine::check`]: the decision, and (for allow/deny
+/// logging) the pattern that produced it.
+#[derive(Debug, Clone)]
+pub struct PathCheck {
+    pub decision: Decision,
+    pub matched_rule: Option<String>,
+}
+
+/// Per-role allow/deny path matchers, loaded from a single TOML file.
+pub struct PathEngine {
+    roles: HashMap<String, TrieNode>,
+}
+
+impl PathEngine {
+    /// Default ACL path-rules file: `<project_dir>/.claude/acl/paths.toml`.
+    pub fn default_path(project_dir: &Path) -> PathBuf {
+        project_dir.join(".claude").join("acl").join("paths.toml")
+    }
+
+    /// Load role patterns from `path`. A missing or unparsable file falls
+    /// back to the built-in defaults that `is_allowed_file` used to
+    /// hardcode: Conductor may touch `.claude/`, `memory/`, and a few
+    /// top-level config files; Musician may touch anything.
+    pub fn load(path: &Path) -> Self {
+        let parsed = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str::<PathAclFile>(&contents).ok());
+        let roles = match parsed {
+            Some(file) if !file.roles.is_empty() => file.roles,
+            _ => default_role_patterns(),
+        };
+
+        let mut tries = HashMap::new();
+        for (role, patterns) in roles {
+            let mut trie = TrieNode::default();
+            for pattern in &patterns.allow {
+                trie.insert(&split_pattern(pattern), Decision::Allow, pattern);
+            }
+            for pattern in &patterns.deny {
+                trie.insert(&split_pattern(pattern), Decision::Deny, pattern);
+            }
+            tries.insert(role, trie);
+        }
+        Self { roles: tries }
+    }
+
+    /// Check whether `role` may touch `path`. An unrecognized role, or a
+    /// path matching no rule, denies.
+    pub fn check(&self, role: &str, path: &str) -> PathCheck {
+        let components = normalize_components(path);
+        let components: Vec<&str> = components.iter().map(|s| s.as_str()).collect();
+
+        let Some(trie) = self.roles.get(role) else {
+            return PathCheck { decision: Decision::Deny, matched_rule: None };
+        };
+
+        match best_match(trie, &components, 0) {
+            Some((_, decision, pattern)) => {
+                PathCheck { decision, matched_rule: Some(pattern.to_string()) }
+            }
+            None => PathCheck { decision: Decision::Deny, matched_rule: None },
+        }
+    }
+}
+
+fn default_role_patterns() -> HashMap<String, RolePatterns> {
+    let mut roles = HashMap::new();
+    roles.insert(
+        "conductor".to_string(),
+        RolePatterns {
+            allow: vec![
+                ".claude/**".to_string(),
+                "memory/**".to_string(),
+                "pyproject.toml".to_string(),
+                "settings.json".to_string(),
+                ".gitignore".to_string(),
+            ],
+            deny: vec![],
+        },
+    );
+    roles.insert("musician".to_string(), RolePatterns { allow: vec!["**".to_string()], deny: vec![] });
+    roles
+}
+
+pub(crate) fn split_pattern(pattern: &str) -> Vec<&str> {
+    pattern.split('/').filter(|c| !c.is_empty()).collect()
+}
+
+/// Whether a single `/`-separated `pattern` (a literal component must match
+/// exactly, `*` matches exactly one component, and a trailing `**` matches
+/// any number of remaining components) matches `path`, after normalizing
+/// `path` the same way [`PathEngine::check`] does. Shared by `capabilities`
+/// so its manifest-driven per-permission globs stay consistent with
+/// `PathEngine`'s trie-based role rules instead of drifting apart.
+pub(crate) fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let pattern_parts = split_pattern(pattern);
+    let path_parts = normalize_components(path);
+    let path_parts: Vec<&str> = path_parts.iter().map(|s| s.as_str()).collect();
+    matches_components(&pattern_parts, &path_parts)
+}
+
+fn matches_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", _)) => true,
+        Some((&"*", rest)) => !path.is_empty() && matches_components(rest, &path[1..]),
+        Some((literal, rest)) => path.first() == Some(literal) && matches_components(rest, &path[1..]),
+    }
+}
+
+/// Lexically normalize `path` into plain component strings: drop `.` and
+/// any root/prefix, and resolve `..` by popping the previous component, so
+/// absolute and relative paths (and paths containing `..`) that refer to
+/// the same logical location match the same rule.
+fn normalize_components(path: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(s) => out.push(s.to_string_lossy().into_owned()),
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    out
+}
+
+fn best_match<'a>(
+    node: &'a TrieNode,
+    components: &[&str],
+    depth: usize,
+) -> Option<(usize, Decision, &'a str)> {
+    let mut best: Option<(usize, Decision, &str)> = None;
+
+    if let Some(double_star) = node.children.get("**") {
+        if let Some((decision, pattern)) = &double_star.rule {
+            update_best(&mut best, (depth + 1, *decision, pattern.as_str()));
+        }
+    }
+
+    match components.split_first() {
+        None => {
+            if let Some((decision, pattern)) = &node.rule {
+                update_best(&mut best, (depth, *decision, pattern.as_str()));
+            }
+        }
+        Some((first, rest)) => {
+            if let Some(child) = node.children.get(*first) {
+                if let Some(candidate) = best_match(child, rest, depth + 1) {
+                    update_best(&mut best, candidate);
+                }
+            }
+            if let Some(child) = node.children.get("*") {
+                if let Some(candidate) = best_match(child, rest, depth + 1) {
+                    update_best(&mut best, candidate);
+                }
+            }
+        }
+    }
+
+    best
+}
+
+fn update_best<'a>(best: &mut Option<(usize, Decision, &'a str)>, candidate: (usize, Decision, &'a str)) {
+    match best {
+        None => *best = Some(candidate),
+        Some((best_depth, best_decision, _)) => {
+            let (depth, decision, _) = candidate;
+            if depth > *best_depth || (depth == *best_depth && decision == Decision::Deny && *best_decision == Decision::Allow)
+            {
+                *best = Some(candidate);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine_from_toml(contents: &str) -> PathEngine {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("paths.toml");
+        std::fs::write(&path, contents).unwrap();
+        PathEngine::load(&path)
+    }
+
+    #[test]
+    fn test_missing_file_falls_back_to_defaults() {
+        let engine = PathEngine::load(Path::new("/nonexistent/paths.toml"));
+        assert_eq!(engine.check("conductor", ".claude/rules/test.md").decision, Decision::Allow);
+        assert_eq!(engine.check("conductor", "src/main.rs").decision, Decision::Deny);
+        assert_eq!(engine.check("musician", "src/main.rs").decision, Decision::Allow);
+    }
+
+    #[test]
+    fn test_literal_file_allowed() {
+        let engine = PathEngine::load(Path::new("/nonexistent/paths.toml"));
+        assert_eq!(engine.check("conductor", "pyproject.toml").decision, Decision::Allow);
+        assert_eq!(engine.check("conductor", ".gitignore").decision, Decision::Allow);
+    }
+
+    #[test]
+    fn test_custom_role_patterns() {
+        let engine = engine_from_toml(
+            r#"
+            [reviewer]
+            allow = ["docs/**"]
+            "#,
+        );
+        assert_eq!(engine.check("reviewer", "docs/guide.md").decision, Decision::Allow);
+        assert_eq!(engine.check("reviewer", "src/main.rs").decision, Decision::Deny);
+    }
+
+    #[test]
+    fn test_deny_wins_at_equal_specificity() {
+        let engine = engine_from_toml(
+            r#"
+            [conductor]
+            allow = ["secrets/*"]
+            deny = ["secrets/*"]
+            "#,
+        );
+        assert_eq!(engine.check("conductor", "secrets/token").decision, Decision::Deny);
+    }
+
+    #[test]
+    fn test_longest_match_wins() {
+        let engine = engine_from_toml(
+            r#"
+            [conductor]
+            allow = ["**", "secrets/**"]
+            deny = ["secrets/**"]
+            "#,
+        );
+        // "secrets/**" is more specific than the blanket "**" allow, so the
+        // deny wins even though there's a broader allow rule too.
+        assert_eq!(engine.check("conductor", "secrets/token").decision, Decision::Deny);
+        assert_eq!(engine.check("conductor", "src/main.rs").decision, Decision::Allow);
+    }
+
+    #[test]
+    fn test_unknown_role_denies() {
+        let engine = PathEngine::load(Path::new("/nonexistent/paths.toml"));
+        assert_eq!(engine.check("reviewer", "docs/guide.md").decision, Decision::Deny);
+    }
+
+    #[test]
+    fn test_normalizes_dot_dot_and_absolute_paths() {
+        let engine = engine_from_toml(
+            r#"
+            [conductor]
+            allow = ["docs/**"]
+            "#,
+        );
+        assert_eq!(engine.check("conductor", "/docs/guide.md").decision, Decision::Allow);
+        assert_eq!(engine.check("conductor", "src/../docs/guide.md").decision, Decision::Allow);
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches(".claude/**", ".claude/rules/test.md"));
+        assert!(pattern_matches("pyproject.toml", "pyproject.toml"));
+        assert!(!pattern_matches("pyproject.toml", "src/pyproject.toml"));
+        assert!(pattern_matches("docs/*", "docs/guide.md"));
+        assert!(!pattern_matches("docs/*", "docs/nested/guide.md"));
+    }
+
+    #[test]
+    fn test_pattern_matches_normalizes_the_candidate_path() {
+        // Same normalization PathEngine::check applies to candidate paths:
+        // drop a leading `.`/root, and resolve `..` by popping a component.
+        assert!(pattern_matches("docs/**", "/docs/guide.md"));
+        assert!(pattern_matches("docs/**", "src/../docs/guide.md"));
+    }
+}
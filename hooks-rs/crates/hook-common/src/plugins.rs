@@ -0,0 +1,267 @@
+//! Discovers and drives external analyzer plugins, turning the fixed set
+//! of hook binaries into an extensible pipeline without recompiling this
+//! crate.
+//!
+//! A plugin is any executable under the plugin directory. On discovery it
+//! is spawned (see `subprocess::Plugin` for the line-delimited JSON-RPC
+//! wire protocol) and sent a `{"method":"config"}` handshake to learn
+//! which `tool_name`s (or `"*"` for all) it wants to see. For a matching
+//! `HookInput`, [`PluginRegistry::evaluate`] sends
+//! `{"method":"evaluate","params":<HookInput>}` and expects either a full
+//! `HookOutput` shape back, or a simplified `{"decision":"allow"|"deny",
+//! "context":"..."}`. Like nushell's plugin loader, a plugin that fails to
+//! spawn, fails its handshake, crashes mid-call, or returns malformed JSON
+//! is logged to stderr and skipped — it never blocks the hook pipeline.
+
+use crate::input::HookInput;
+use crate::output::HookOutput;
+use crate::subprocess::Plugin;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Deserialize)]
+struct PluginConfigResponse {
+    #[serde(default)]
+    tool_names: Vec<String>,
+}
+
+struct RegisteredPlugin {
+    name: String,
+    plugin: Plugin,
+    tool_names: Vec<String>,
+}
+
+/// Discovers plugin executables from a directory and dispatches
+/// `HookInput`s to the ones that asked for them.
+pub struct PluginRegistry {
+    plugins: Vec<RegisteredPlugin>,
+}
+
+impl PluginRegistry {
+    /// Default plugin directory: `~/.config/ai/hooks/plugins`.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("ai").join("hooks").join("plugins"))
+    }
+
+    /// Spawn and handshake with every executable file directly under
+    /// `plugin_dir`. A missing directory yields an empty (no-op) registry;
+    /// a plugin that fails to spawn or answer the `config` handshake is
+    /// logged and skipped.
+    pub fn discover(plugin_dir: &Path) -> Self {
+        let mut plugins = Vec::new();
+
+        let Ok(entries) = std::fs::read_dir(plugin_dir) else {
+            return Self { plugins };
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !is_executable(&path) {
+                continue;
+            }
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            let Some(path_str) = path.to_str() else { continue };
+
+            let mut plugin = match Plugin::spawn(path_str) {
+                Ok(plugin) => plugin,
+                Err(e) => {
+                    eprintln!("Warning: failed to spawn plugin '{}': {}", name, e);
+                    continue;
+                }
+            };
+
+            let tool_names = match plugin.call("config", serde_json::json!({})) {
+                Ok(value) => serde_json::from_value::<PluginConfigResponse>(value)
+                    .map(|config| config.tool_names)
+                    .unwrap_or_else(|e| {
+                        eprintln!("Warning: plugin '{}' returned malformed config: {}", name, e);
+                        Vec::new()
+                    }),
+                Err(e) => {
+                    eprintln!("Warning: plugin '{}' failed config handshake: {}", name, e);
+                    continue;
+                }
+            };
+
+            plugins.push(RegisteredPlugin { name, plugin, tool_names });
+        }
+
+        Self { plugins }
+    }
+
+    /// Ask every plugin registered for `input.tool_name` (or `"*"`) to
+    /// evaluate it, in registration order, returning the first non-silent
+    /// decision. A plugin that crashes or returns malformed JSON is logged
+    /// and skipped rather than treated as fatal.
+    pub fn evaluate(&mut self, input: &HookInput) -> Option<HookOutput> {
+        for registered in &mut self.plugins {
+            let wants_event =
+                registered.tool_names.iter().any(|t| t == "*" || t == &input.tool_name);
+            if !wants_event {
+                continue;
+            }
+
+            let params = match serde_json::to_value(input) {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+
+            let result = match registered.plugin.call("evaluate", params) {
+                Ok(value) => value,
+                Err(e) => {
+                    eprintln!("Warning: plugin '{}' evaluate call failed: {}", registered.name, e);
+                    continue;
+                }
+            };
+
+            if let Some(output) = parse_plugin_decision(&result) {
+                return Some(output);
+            }
+        }
+
+        None
+    }
+}
+
+/// Parse a plugin's `evaluate` result into a `HookOutput`: either a full
+/// `HookOutput` shape, or the simplified `{"decision": "allow"|"deny",
+/// "context": "..."}`. Anything else (including `null`, meaning "no
+/// opinion") yields `None`.
+fn parse_plugin_decision(value: &serde_json::Value) -> Option<HookOutput> {
+    if value.is_null() {
+        return None;
+    }
+
+    if let Ok(output) = serde_json::from_value::<HookOutput>(value.clone()) {
+        return Some(output);
+    }
+
+    let decision = value.get("decision").and_then(|v| v.as_str())?;
+    let output = match decision {
+        "deny" => HookOutput::deny(),
+        "allow" => HookOutput::allow(),
+        _ => return None,
+    };
+
+    match value.get("context").and_then(|v| v.as_str()) {
+        Some(context) => Some(output.with_context(context)),
+        None => Some(output),
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_missing_dir_yields_empty_registry() {
+        let registry = PluginRegistry::discover(Path::new("/nonexistent/plugin/dir"));
+        let mut registry = registry;
+        let input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        assert!(registry.evaluate(&input).is_none());
+    }
+
+    #[test]
+    fn test_parse_plugin_decision_full_hook_output() {
+        let value = serde_json::json!({
+            "hookSpecificOutput": {
+                "hookEventName": "PreToolUse",
+                "permissionDecision": "deny",
+                "additionalContext": "blocked by plugin"
+            }
+        });
+        let output = parse_plugin_decision(&value).unwrap();
+        assert_eq!(
+            output.hook_specific_output.permission_decision,
+            Some(crate::output::PermissionDecision::Deny)
+        );
+    }
+
+    #[test]
+    fn test_parse_plugin_decision_simplified_shape() {
+        let value = serde_json::json!({"decision": "allow", "context": "looks fine"});
+        let output = parse_plugin_decision(&value).unwrap();
+        assert_eq!(
+            output.hook_specific_output.additional_context.as_deref(),
+            Some("looks fine")
+        );
+    }
+
+    #[test]
+    fn test_parse_plugin_decision_null_is_none() {
+        assert!(parse_plugin_decision(&serde_json::Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_parse_plugin_decision_malformed_is_none() {
+        assert!(parse_plugin_decision(&serde_json::json!({"wat": true})).is_none());
+    }
+
+    /// Write an executable plugin script that answers `config` with a
+    /// `tool_names` list and `evaluate` with a fixed deny decision.
+    fn deny_plugin_script() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deny-plugin.sh");
+        std::fs::write(
+            &path,
+            r#"#!/bin/sh
+while IFS= read -r line; do
+  id=$(echo "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"method":"config"'*)
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"tool_names\":[\"Bash\"]}}"
+      ;;
+    *)
+      echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"decision\":\"deny\",\"context\":\"blocked\"}}"
+      ;;
+  esac
+done
+"#,
+        )
+        .unwrap();
+        let mut perms = std::fs::metadata(&path).unwrap().permissions();
+        std::os::unix::fs::PermissionsExt::set_mode(&mut perms, 0o755);
+        std::fs::set_permissions(&path, perms).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_evaluate_matching_tool_returns_plugin_decision() {
+        let (_dir, script) = deny_plugin_script();
+        std::fs::create_dir_all(script.parent().unwrap()).unwrap();
+        let plugin_dir = script.parent().unwrap();
+        let mut registry = PluginRegistry::discover(plugin_dir);
+
+        let input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        let decision = registry.evaluate(&input).unwrap();
+        assert_eq!(
+            decision.hook_specific_output.permission_decision,
+            Some(crate::output::PermissionDecision::Deny)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_non_matching_tool_is_none() {
+        let (_dir, script) = deny_plugin_script();
+        let plugin_dir = script.parent().unwrap();
+        let mut registry = PluginRegistry::discover(plugin_dir);
+
+        let input = HookInput { tool_name: "Edit".to_string(), ..Default::default() };
+        assert!(registry.evaluate(&input).is_none());
+    }
+}
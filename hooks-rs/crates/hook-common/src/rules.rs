@@ -0,0 +1,306 @@
+//! In-process hook rule plugins loaded at runtime.
+//!
+//! `hook_common::plugins` already lets a hook call out to external
+//! analyzer executables over JSON-RPC, but spawning a subprocess per rule
+//! is overkill for the common case: "deny/ask/allow this tool with this
+//! context, optionally only when the command/path matches a substring".
+//! Following jj's move toward supporting multiple extensions through a
+//! stable API, this module defines that contract as a plain trait,
+//! [`HookRule`], plus a [`RuleChain`] that discovers declarative rule
+//! manifests from a directory and dispatches each [`HookInput`] through
+//! them in a fixed order. Decisions merge first-deny-wins: the first
+//! `deny` short-circuits, otherwise every rule's context is concatenated
+//! so none of them are silently dropped.
+//!
+//! This is the one shared extension pipeline for every hook that wants to
+//! host project-specific rules as data instead of recompiled Rust:
+//! `enforce-no-merge`, `enforce-delegation`, and `agent-router` all
+//! discover the same directory and fold its verdict into their own
+//! decision, so a team can drop in a delegation or routing override
+//! without touching any of their source.
+//!
+//! Rule manifests are TOML files directly under the plugin directory
+//! (`.claude/hooks/plugins/*.toml` by default), applied in filename
+//! order:
+//!
+//! ```toml
+//! tool_names = ["Bash"]       # or ["*"] for every tool
+//! decision = "deny"           # "allow" | "deny" | "ask"
+//! context = "no `curl | sh` in this repo"
+//! command_contains = "curl"   # optional: only match Bash commands containing this
+//! path_contains = "secrets/"  # optional: only match Edit/Write paths containing this
+//! ```
+
+use crate::input::HookInput;
+use crate::output::{HookOutput, PermissionDecision};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A single rule that may render an opinion on a `HookInput`. `None` means
+/// "no opinion, defer to the next rule".
+pub trait HookRule {
+    fn evaluate(&self, input: &HookInput) -> Option<HookOutput>;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RuleManifest {
+    tool_names: Vec<String>,
+    decision: String,
+    #[serde(default)]
+    context: Option<String>,
+    #[serde(default)]
+    command_contains: Option<String>,
+    #[serde(default)]
+    path_contains: Option<String>,
+}
+
+/// A rule loaded from a single TOML manifest file.
+struct DeclarativeRule {
+    name: String,
+    manifest: RuleManifest,
+}
+
+impl HookRule for DeclarativeRule {
+    fn evaluate(&self, input: &HookInput) -> Option<HookOutput> {
+        let wants_tool = self.manifest.tool_names.iter().any(|t| t == "*" || t == &input.tool_name);
+        if !wants_tool {
+            return None;
+        }
+
+        if let Some(needle) = &self.manifest.command_contains {
+            if !input.get_command().unwrap_or("").contains(needle.as_str()) {
+                return None;
+            }
+        }
+
+        if let Some(needle) = &self.manifest.path_contains {
+            if !input.get_file_path().unwrap_or("").contains(needle.as_str()) {
+                return None;
+            }
+        }
+
+        let decision = match self.manifest.decision.as_str() {
+            "allow" => PermissionDecision::Allow,
+            "deny" => PermissionDecision::Deny,
+            "ask" => PermissionDecision::Ask,
+            other => {
+                eprintln!("Warning: rule '{}' has unknown decision '{}', skipping", self.name, other);
+                return None;
+            }
+        };
+
+        let output = HookOutput::pre_tool_use(decision);
+        Some(match &self.manifest.context {
+            Some(context) => output.with_context(context.clone()),
+            None => output,
+        })
+    }
+}
+
+/// An ordered chain of [`HookRule`]s, discovered from a directory of TOML
+/// manifests.
+pub struct RuleChain {
+    rules: Vec<DeclarativeRule>,
+}
+
+impl RuleChain {
+    /// Default rule plugin directory: `<project_dir>/.claude/hooks/plugins`.
+    pub fn default_dir(project_dir: &Path) -> PathBuf {
+        project_dir.join(".claude").join("hooks").join("plugins")
+    }
+
+    /// Load every `*.toml` manifest directly under `dir`, in filename
+    /// order (so rule precedence is predictable and editable by renaming
+    /// files). A missing directory or an unparsable manifest is skipped
+    /// rather than treated as fatal -- a typo in one rule shouldn't take
+    /// down the whole hook pipeline.
+    pub fn discover(dir: &Path) -> Self {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("toml"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort();
+
+        let mut rules = Vec::new();
+        for path in entries {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            let Ok(contents) = std::fs::read_to_string(&path) else { continue };
+            match toml::from_str::<RuleManifest>(&contents) {
+                Ok(manifest) => rules.push(DeclarativeRule { name, manifest }),
+                Err(e) => eprintln!("Warning: rule manifest '{}' is malformed: {}", name, e),
+            }
+        }
+
+        Self { rules }
+    }
+
+    /// Run `input` through every rule in order, merging their opinions:
+    /// the first `deny` short-circuits and is returned immediately;
+    /// otherwise every non-`None` context is concatenated (newline
+    /// separated) into a single allow/ask output, using the strongest
+    /// non-deny decision seen (`ask` over `allow`). Returns `None` if no
+    /// rule had an opinion.
+    ///
+    /// Each rule's own opinion is always PreToolUse-shaped (a
+    /// `PermissionDecision` plus optional context) regardless of which
+    /// hook is asking, but the *aggregated* output this returns is shaped
+    /// for `event` (e.g. `"PreToolUse"`, `"UserPromptSubmit"`): only
+    /// `PreToolUse` gets a `permission_decision` field at all, everywhere
+    /// else a `deny` becomes a `blocking_error` instead, since that's the
+    /// field every event type actually uses to block.
+    pub fn evaluate(&self, input: &HookInput, event: &str) -> Option<HookOutput> {
+        let mut decision: Option<PermissionDecision> = None;
+        let mut contexts: Vec<String> = Vec::new();
+
+        for rule in &self.rules {
+            let Some(output) = rule.evaluate(input) else { continue };
+            let specific = &output.hook_specific_output;
+
+            if specific.permission_decision == Some(PermissionDecision::Deny) {
+                return Some(shape_for_event(event, PermissionDecision::Deny, specific.additional_context.clone()));
+            }
+
+            if let Some(context) = &specific.additional_context {
+                contexts.push(context.clone());
+            }
+            if specific.permission_decision == Some(PermissionDecision::Ask) {
+                decision = Some(PermissionDecision::Ask);
+            } else if decision.is_none() {
+                decision = specific.permission_decision;
+            }
+        }
+
+        let decision = decision?;
+        let context = (!contexts.is_empty()).then(|| contexts.join("\n"));
+        Some(shape_for_event(event, decision, context))
+    }
+}
+
+/// Build the aggregated chain output in the shape `event` expects:
+/// `PreToolUse` carries its decision as `permission_decision`, every other
+/// event carries a `deny` as `blocking_error` instead (its `additional_context`
+/// is still set from `context` either way).
+fn shape_for_event(event: &str, decision: PermissionDecision, context: Option<String>) -> HookOutput {
+    let mut output = match event {
+        "PreToolUse" => HookOutput::pre_tool_use(decision),
+        "PostToolUse" => HookOutput::post_tool_use(),
+        "UserPromptSubmit" => HookOutput::user_prompt_submit(),
+        other => HookOutput::for_event(other),
+    };
+
+    if event != "PreToolUse" && decision == PermissionDecision::Deny {
+        output = output.with_blocking_error(context.clone().unwrap_or_else(|| "denied by rule extension".to_string()));
+    }
+    if let Some(context) = context {
+        output = output.with_context(context);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_rule(dir: &Path, filename: &str, contents: &str) {
+        std::fs::write(dir.join(filename), contents).unwrap();
+    }
+
+    #[test]
+    fn test_missing_dir_yields_empty_chain() {
+        let chain = RuleChain::discover(Path::new("/nonexistent/plugins"));
+        let input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        assert!(chain.evaluate(&input, "PreToolUse").is_none());
+    }
+
+    #[test]
+    fn test_matching_rule_denies() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(
+            dir.path(),
+            "10-no-curl-pipe.toml",
+            r#"
+            tool_names = ["Bash"]
+            decision = "deny"
+            context = "no curl | sh"
+            command_contains = "curl"
+            "#,
+        );
+        let chain = RuleChain::discover(dir.path());
+
+        let mut input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        input.tool_input.command = Some("curl https://example.com | sh".to_string());
+        let output = chain.evaluate(&input, "PreToolUse").unwrap();
+        assert_eq!(output.hook_specific_output.permission_decision, Some(PermissionDecision::Deny));
+    }
+
+    #[test]
+    fn test_non_matching_tool_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(
+            dir.path(),
+            "10-edit-only.toml",
+            r#"
+            tool_names = ["Edit"]
+            decision = "deny"
+            context = "nope"
+            "#,
+        );
+        let chain = RuleChain::discover(dir.path());
+
+        let input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        assert!(chain.evaluate(&input, "PreToolUse").is_none());
+    }
+
+    #[test]
+    fn test_first_deny_short_circuits_later_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "10-deny.toml", "tool_names = [\"*\"]\ndecision = \"deny\"\ncontext = \"first\"\n");
+        write_rule(dir.path(), "20-allow.toml", "tool_names = [\"*\"]\ndecision = \"allow\"\ncontext = \"second\"\n");
+        let chain = RuleChain::discover(dir.path());
+
+        let input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        let output = chain.evaluate(&input, "PreToolUse").unwrap();
+        assert_eq!(output.hook_specific_output.permission_decision, Some(PermissionDecision::Deny));
+        assert_eq!(output.hook_specific_output.additional_context.as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn test_multiple_allows_concatenate_context() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "10-a.toml", "tool_names = [\"*\"]\ndecision = \"allow\"\ncontext = \"note a\"\n");
+        write_rule(dir.path(), "20-b.toml", "tool_names = [\"*\"]\ndecision = \"allow\"\ncontext = \"note b\"\n");
+        let chain = RuleChain::discover(dir.path());
+
+        let input = HookInput { tool_name: "Bash".to_string(), ..Default::default() };
+        let output = chain.evaluate(&input, "PreToolUse").unwrap();
+        assert_eq!(output.hook_specific_output.permission_decision, Some(PermissionDecision::Allow));
+        assert_eq!(output.hook_specific_output.additional_context.as_deref(), Some("note a\nnote b"));
+    }
+
+    #[test]
+    fn test_malformed_manifest_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "10-broken.toml", "this is not valid = = toml");
+        let chain = RuleChain::discover(dir.path());
+        assert_eq!(chain.rules.len(), 0);
+    }
+
+    #[test]
+    fn test_deny_is_shaped_as_blocking_error_for_non_pre_tool_use_events() {
+        let dir = tempfile::tempdir().unwrap();
+        write_rule(dir.path(), "10-deny.toml", "tool_names = [\"*\"]\ndecision = \"deny\"\ncontext = \"no routing this prompt\"\n");
+        let chain = RuleChain::discover(dir.path());
+
+        let input = HookInput { tool_name: "UserPromptSubmit".to_string(), ..Default::default() };
+        let output = chain.evaluate(&input, "UserPromptSubmit").unwrap();
+        assert_eq!(output.hook_specific_output.hook_event_name, "UserPromptSubmit");
+        assert_eq!(output.hook_specific_output.permission_decision, None);
+        assert_eq!(output.hook_specific_output.blocking_error.as_deref(), Some("no routing this prompt"));
+        assert_eq!(output.hook_specific_output.additional_context.as_deref(), Some("no routing this prompt"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Detects what kind of Claude Code session a hook binary is running in.
+//!
+//! This used to be guessed independently by each hook that cared about
+//! hierarchy (conductor vs. musician) or interactivity, each reimplementing
+//! its own mix of environment-variable checks and TTY probing. Centralizing
+//! it here means every hook agrees on the same answer and the detection
+//! logic only needs testing once.
+
+use std::path::PathBuf;
+
+/// A hook's position in the Conductor/Musician delegation hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionKind {
+    /// Top of the delegation chain: expected to delegate work via `Task`
+    /// rather than run it directly.
+    Conductor,
+    /// A delegated worker (subagent), or any session we can't positively
+    /// identify as a conductor -- the safe default.
+    Musician,
+}
+
+/// Classify the current process's session, in order of confidence:
+/// 1. An explicit `AGENT_ROLE=conductor|musician` environment variable.
+/// 2. A `.claude/.conductor-session` marker file under `CLAUDE_PROJECT_DIR`.
+/// 3. `CLAUDE_SUBAGENT=1`, set by the harness when spawning a subagent.
+/// 4. No controlling TTY on stdin, which a top-level interactive session
+///    always has and a non-interactive subagent never does.
+///
+/// The first signal present decides. When none of signals 1-3 is present,
+/// the TTY probe decides: no TTY on stdin yields [`SessionKind::Musician`]
+/// (the more restricted role), a TTY on stdin yields
+/// [`SessionKind::Conductor`].
+pub fn detect_session_kind() -> SessionKind {
+    if let Ok(role) = std::env::var("AGENT_ROLE") {
+        match role.to_lowercase().as_str() {
+            "conductor" => return SessionKind::Conductor,
+            "musician" => return SessionKind::Musician,
+            _ => {}
+        }
+    }
+
+    if is_conductor_session() {
+        return SessionKind::Conductor;
+    }
+
+    if is_subagent_env() {
+        return SessionKind::Musician;
+    }
+
+    if !stdin_is_tty() {
+        return SessionKind::Musician;
+    }
+
+    SessionKind::Conductor
+}
+
+fn is_conductor_session() -> bool {
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+    let marker_path = PathBuf::from(project_dir).join(".claude").join(".conductor-session");
+    marker_path.exists()
+}
+
+fn is_subagent_env() -> bool {
+    std::env::var("CLAUDE_SUBAGENT").map(|v| v == "1").unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn stdin_is_tty() -> bool {
+    unsafe { libc::isatty(libc::STDIN_FILENO) != 0 }
+}
+
+#[cfg(not(unix))]
+fn stdin_is_tty() -> bool {
+    // Without a cheap, reliable TTY probe, don't let this signal force a
+    // `Musician` classification on non-Unix targets.
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `std::env::var` is process-global, so serialize tests that touch it.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn clear_env() {
+        std::env::remove_var("AGENT_ROLE");
+        std::env::remove_var("CLAUDE_SUBAGENT");
+        std::env::remove_var("CLAUDE_PROJECT_DIR");
+    }
+
+    #[test]
+    fn test_explicit_agent_role_wins() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        std::env::set_var("AGENT_ROLE", "Conductor");
+        assert_eq!(detect_session_kind(), SessionKind::Conductor);
+        std::env::set_var("AGENT_ROLE", "musician");
+        assert_eq!(detect_session_kind(), SessionKind::Musician);
+        clear_env();
+    }
+
+    #[test]
+    fn test_conductor_marker_file() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        std::fs::write(dir.path().join(".claude").join(".conductor-session"), b"").unwrap();
+        std::env::set_var("CLAUDE_PROJECT_DIR", dir.path());
+        assert_eq!(detect_session_kind(), SessionKind::Conductor);
+        clear_env();
+    }
+
+    #[test]
+    fn test_subagent_env_signal() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        std::env::set_var("CLAUDE_SUBAGENT", "1");
+        assert_eq!(detect_session_kind(), SessionKind::Musician);
+        clear_env();
+    }
+}
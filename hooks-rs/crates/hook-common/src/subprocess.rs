@@ -0,0 +1,950 @@
+//! Subprocess execution utilities.
+
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Output, Stdio};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+/// Result of a command execution.
+#[derive(Debug, Clone)]
+pub struct CommandResult {
+    /// Exit code (None if killed by signal)
+    pub exit_code: Option<i32>,
+    /// Standard output
+    pub stdout: String,
+    /// Standard error
+    pub stderr: String,
+    /// Whether the command succeeded (exit code 0)
+    pub success: bool,
+    /// Whether the command was killed after exceeding its timeout (only
+    /// ever set by [`run_command_with_timeout`],
+    /// [`run_command_with_timeout_streaming`] and [`run_command_sandboxed`])
+    pub timed_out: bool,
+    /// Whether the command appears to have been killed by one of its
+    /// [`Limits`] rather than exiting or timing out on its own (only ever
+    /// set by [`run_command_sandboxed`]).
+    pub resource_limited: bool,
+}
+
+impl CommandResult {
+    /// Create from std::process::Output.
+    pub fn from_output(output: Output) -> Self {
+        Self {
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            success: output.status.success(),
+            timed_out: false,
+            resource_limited: false,
+        }
+    }
+}
+
+/// Run a shell command and return the result.
+pub fn run_command(cmd: &str) -> Result<CommandResult> {
+    let output = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", cmd]).output()
+    } else {
+        Command::new("sh").args(["-c", cmd]).output()
+    }
+    .with_context(|| format!("Failed to execute command: {}", cmd))?;
+
+    Ok(CommandResult::from_output(output))
+}
+
+/// Grace period between `SIGTERM` and `SIGKILL` when a timed-out command's
+/// process group doesn't exit on its own (Unix only).
+const KILL_GRACE_PERIOD: Duration = Duration::from_millis(2000);
+
+/// Which stream a line read by [`run_command_with_timeout_streaming`] came
+/// from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    Stdout,
+    Stderr,
+}
+
+/// A line drained from the child's stdout/stderr, or the reason a drain
+/// thread stopped. Mirrors the `PluginLine` reader-thread pattern used by
+/// [`Plugin`].
+enum DrainEvent {
+    Line(StreamKind, String),
+    Closed(StreamKind),
+}
+
+fn spawn_drain_thread(
+    kind: StreamKind,
+    mut reader: impl BufRead + Send + 'static,
+    tx: std::sync::mpsc::Sender<DrainEvent>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let text = line.trim_end_matches(['\n', '\r']).to_string();
+                    if tx.send(DrainEvent::Line(kind, text)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        let _ = tx.send(DrainEvent::Closed(kind));
+    })
+}
+
+/// Run a shell command with a timeout, draining stdout/stderr as it runs
+/// rather than waiting until exit. A command that writes more than the OS
+/// pipe buffer (a failing `pytest`/`cargo test` run easily does) would
+/// otherwise block on a full pipe forever while we only poll `try_wait`,
+/// making us report a false timeout. On a real timeout, the command's
+/// whole process group (Unix) or Job Object (Windows) is terminated, not
+/// just the immediate `sh -c`/`cmd /C` wrapper, so grandchildren don't
+/// survive as orphans. The timeout itself is not an error: the returned
+/// [`CommandResult`] has `timed_out: true` and `success: false` with
+/// whatever output was captured before the kill, and callers decide how
+/// to report it.
+pub fn run_command_with_timeout(cmd: &str, timeout: Duration) -> Result<CommandResult> {
+    run_command_with_timeout_streaming(cmd, timeout, |_, _| {})
+}
+
+/// Like [`run_command_with_timeout`], but calls `on_line` as each line of
+/// output arrives instead of only returning the buffered total once the
+/// command finishes. Lets callers like the codex-suggest hook process
+/// output incrementally.
+pub fn run_command_with_timeout_streaming(
+    cmd: &str,
+    timeout: Duration,
+    mut on_line: impl FnMut(StreamKind, &str),
+) -> Result<CommandResult> {
+    use std::process::Stdio;
+    use std::sync::mpsc;
+
+    let mut command = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    };
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    #[cfg(unix)]
+    put_in_new_process_group(&mut command);
+
+    let mut child =
+        command.spawn().with_context(|| format!("Failed to spawn command: {}", cmd))?;
+
+    #[cfg(windows)]
+    let job = JobHandle::new_assigned(&child);
+
+    let stdout = child.stdout.take().expect("child spawned with piped stdout");
+    let stderr = child.stderr.take().expect("child spawned with piped stderr");
+
+    let (tx, rx) = mpsc::channel();
+    let stdout_thread =
+        spawn_drain_thread(StreamKind::Stdout, BufReader::new(stdout), tx.clone());
+    let stderr_thread = spawn_drain_thread(StreamKind::Stderr, BufReader::new(stderr), tx);
+
+    let mut stdout_buf = String::new();
+    let mut stderr_buf = String::new();
+    let mut stdout_closed = false;
+    let mut stderr_closed = false;
+    let mut timed_out = false;
+    let start = std::time::Instant::now();
+
+    loop {
+        match rx.recv_timeout(Duration::from_millis(20)) {
+            Ok(DrainEvent::Line(kind, text)) => {
+                on_line(kind, &text);
+                let buf = match kind {
+                    StreamKind::Stdout => &mut stdout_buf,
+                    StreamKind::Stderr => &mut stderr_buf,
+                };
+                buf.push_str(&text);
+                buf.push('\n');
+            }
+            Ok(DrainEvent::Closed(StreamKind::Stdout)) => stdout_closed = true,
+            Ok(DrainEvent::Closed(StreamKind::Stderr)) => stderr_closed = true,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                stdout_closed = true;
+                stderr_closed = true;
+            }
+        }
+
+        if !timed_out && start.elapsed() > timeout {
+            timed_out = true;
+            #[cfg(unix)]
+            terminate_process_group(&mut child, KILL_GRACE_PERIOD);
+            #[cfg(windows)]
+            {
+                if let Some(job) = &job {
+                    job.terminate();
+                } else {
+                    let _ = child.kill();
+                }
+            }
+        }
+
+        if stdout_closed && stderr_closed {
+            break;
+        }
+    }
+
+    let _ = stdout_thread.join();
+    let _ = stderr_thread.join();
+    let status = child.wait().context("Failed to wait for command")?;
+
+    Ok(if timed_out {
+        CommandResult {
+            exit_code: None,
+            stdout: stdout_buf,
+            stderr: stderr_buf,
+            success: false,
+            timed_out: true,
+            resource_limited: false,
+        }
+    } else {
+        CommandResult {
+            exit_code: status.code(),
+            stdout: stdout_buf,
+            stderr: stderr_buf,
+            success: status.success(),
+            timed_out: false,
+            resource_limited: false,
+        }
+    })
+}
+
+/// Resource limits applied to a [`run_command_sandboxed`] child via
+/// `setrlimit` (Unix only; a no-op elsewhere). Every limit is optional so
+/// callers can tighten only what matters for their use case; [`Default`]
+/// gives sane guardrails for a hook that shells out to something it didn't
+/// write.
+#[derive(Debug, Clone)]
+pub struct Limits {
+    /// `RLIMIT_CPU`: total CPU seconds before the kernel sends `SIGXCPU`
+    /// (soft limit) then `SIGKILL` (hard limit, same value here).
+    pub cpu_seconds: Option<u64>,
+    /// `RLIMIT_AS`: maximum virtual address space in bytes.
+    pub max_address_space_bytes: Option<u64>,
+    /// `RLIMIT_FSIZE`: maximum size in bytes of any file the command
+    /// writes; exceeding it raises `SIGXFSZ`.
+    pub max_output_file_bytes: Option<u64>,
+    /// `RLIMIT_NOFILE`: maximum number of open file descriptors.
+    pub max_open_files: Option<u64>,
+    /// Wall-clock timeout, enforced the same way as
+    /// [`run_command_with_timeout`] (process-group `SIGTERM` then
+    /// `SIGKILL`), since `setrlimit` alone can't bound a command that's
+    /// merely stuck (e.g. blocked on network I/O).
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            cpu_seconds: Some(60),
+            max_address_space_bytes: Some(1 << 30),
+            max_output_file_bytes: Some(50 * 1024 * 1024),
+            max_open_files: Some(256),
+            timeout: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+impl Limits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cpu_seconds(mut self, secs: u64) -> Self {
+        self.cpu_seconds = Some(secs);
+        self
+    }
+
+    pub fn max_address_space_bytes(mut self, bytes: u64) -> Self {
+        self.max_address_space_bytes = Some(bytes);
+        self
+    }
+
+    pub fn max_output_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_output_file_bytes = Some(bytes);
+        self
+    }
+
+    pub fn max_open_files(mut self, n: u64) -> Self {
+        self.max_open_files = Some(n);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+#[cfg(unix)]
+fn set_rlimit(resource: libc::c_uint, limit: u64) -> std::io::Result<()> {
+    let rlim = libc::rlimit { rlim_cur: limit, rlim_max: limit };
+    if unsafe { libc::setrlimit(resource, &rlim) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Apply `limits`' rlimits to the about-to-exec child. Installed as part of
+/// the same `pre_exec` closure as [`put_in_new_process_group`] so the
+/// sandboxed child is both group-isolated (for the timeout path) and
+/// resource-bounded.
+#[cfg(unix)]
+fn apply_limits_pre_exec(command: &mut Command, limits: Limits) {
+    use std::os::unix::process::CommandExt;
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setpgid(0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            if let Some(secs) = limits.cpu_seconds {
+                set_rlimit(libc::RLIMIT_CPU, secs)?;
+            }
+            if let Some(bytes) = limits.max_address_space_bytes {
+                set_rlimit(libc::RLIMIT_AS, bytes)?;
+            }
+            if let Some(bytes) = limits.max_output_file_bytes {
+                set_rlimit(libc::RLIMIT_FSIZE, bytes)?;
+            }
+            if let Some(n) = limits.max_open_files {
+                set_rlimit(libc::RLIMIT_NOFILE, n)?;
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Unix signals that indicate a `setrlimit`-imposed limit killed the
+/// command, as opposed to it exiting, being timed out, or being killed for
+/// an unrelated reason.
+#[cfg(unix)]
+fn signal_from_resource_limit(signal: i32) -> bool {
+    matches!(signal, libc::SIGXCPU | libc::SIGXFSZ | libc::SIGKILL | libc::SIGSEGV)
+}
+
+/// Run a shell command under [`Limits`], draining output the same way as
+/// [`run_command_with_timeout`]. On Unix the child is placed in its own
+/// process group with `setrlimit`-based CPU/memory/file-size/fd-count
+/// bounds applied before exec; a breached limit surfaces as
+/// `resource_limited: true` on the returned [`CommandResult`] (best-effort,
+/// based on the terminating signal) rather than as an `anyhow` error, the
+/// same way timeouts do. `limits.timeout`, if set, reuses the
+/// process-group kill path from `run_command_with_timeout` for commands
+/// that are merely stuck rather than over a resource budget.
+pub fn run_command_sandboxed(cmd: &str, limits: Limits) -> Result<CommandResult> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    };
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let timeout = limits.timeout;
+    #[cfg(unix)]
+    apply_limits_pre_exec(&mut command, limits);
+
+    let mut child =
+        command.spawn().with_context(|| format!("Failed to spawn command: {}", cmd))?;
+
+    #[cfg(windows)]
+    let job = JobHandle::new_assigned(&child);
+
+    let stdout = child.stdout.take().expect("child spawned with piped stdout");
+    let stderr = child.stderr.take().expect("child spawned with piped stderr");
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let stdout_thread =
+        spawn_drain_thread(StreamKind::Stdout, BufReader::new(stdout), tx.clone());
+    let stderr_thread = spawn_drain_thread(StreamKind::Stderr, BufReader::new(stderr), tx);
+
+    let mut stdout_buf = String::new();
+    let mut stderr_buf = String::new();
+    let mut stdout_closed = false;
+    let mut stderr_closed = false;
+    let mut timed_out = false;
+    let start = std::time::Instant::now();
+
+    loop {
+        match rx.recv_timeout(Duration::from_millis(20)) {
+            Ok(DrainEvent::Line(kind, text)) => {
+                let buf = match kind {
+                    StreamKind::Stdout => &mut stdout_buf,
+                    StreamKind::Stderr => &mut stderr_buf,
+                };
+                buf.push_str(&text);
+                buf.push('\n');
+            }
+            Ok(DrainEvent::Closed(StreamKind::Stdout)) => stdout_closed = true,
+            Ok(DrainEvent::Closed(StreamKind::Stderr)) => stderr_closed = true,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                stdout_closed = true;
+                stderr_closed = true;
+            }
+        }
+
+        if !timed_out {
+            if let Some(timeout) = timeout {
+                if start.elapsed() > timeout {
+                    timed_out = true;
+                    #[cfg(unix)]
+                    terminate_process_group(&mut child, KILL_GRACE_PERIOD);
+                    #[cfg(windows)]
+                    {
+                        if let Some(job) = &job {
+                            job.terminate();
+                        } else {
+                            let _ = child.kill();
+                        }
+                    }
+                }
+            }
+        }
+
+        if stdout_closed && stderr_closed {
+            break;
+        }
+    }
+
+    let _ = stdout_thread.join();
+    let _ = stderr_thread.join();
+    let status = child.wait().context("Failed to wait for command")?;
+
+    #[cfg(unix)]
+    let resource_limited = !timed_out && {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal().map(signal_from_resource_limit).unwrap_or(false)
+    };
+    #[cfg(not(unix))]
+    let resource_limited = false;
+
+    Ok(CommandResult {
+        exit_code: status.code(),
+        stdout: stdout_buf,
+        stderr: stderr_buf,
+        success: status.success() && !resource_limited,
+        timed_out,
+        resource_limited,
+    })
+}
+
+/// Put `command`'s future child in its own process group (`setpgid(0, 0)`
+/// right after fork, before exec), so a timeout can signal the whole
+/// group rather than only the direct child.
+#[cfg(unix)]
+fn put_in_new_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setpgid(0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Send `SIGTERM` to `child`'s process group, wait up to `grace_period`
+/// for it to exit, then escalate to `SIGKILL` on the group if it hasn't.
+#[cfg(unix)]
+fn terminate_process_group(child: &mut Child, grace_period: Duration) {
+    let pgid = child.id() as libc::pid_t;
+    unsafe {
+        libc::kill(-pgid, libc::SIGTERM);
+    }
+
+    let deadline = std::time::Instant::now() + grace_period;
+    while std::time::Instant::now() < deadline {
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+}
+
+/// Windows equivalent of a process group: a Job Object that the child (and
+/// anything it spawns, by default job settings) is assigned to, so
+/// terminating the job terminates the whole tree.
+#[cfg(windows)]
+struct JobHandle(windows_sys::Win32::Foundation::HANDLE);
+
+#[cfg(windows)]
+impl JobHandle {
+    /// Create a Job Object and assign `child` to it. Returns `None` if
+    /// either step fails, in which case callers should fall back to
+    /// killing just the direct child.
+    fn new_assigned(child: &Child) -> Option<Self> {
+        use std::os::windows::io::AsRawHandle;
+        use windows_sys::Win32::System::JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW,
+        };
+
+        unsafe {
+            let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+            if job == 0 {
+                return None;
+            }
+            if AssignProcessToJobObject(job, child.as_raw_handle() as _) == 0 {
+                windows_sys::Win32::Foundation::CloseHandle(job);
+                return None;
+            }
+            Some(Self(job))
+        }
+    }
+
+    fn terminate(&self) {
+        unsafe {
+            windows_sys::Win32::System::JobObjects::TerminateJobObject(self.0, 1);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        unsafe {
+            windows_sys::Win32::Foundation::CloseHandle(self.0);
+        }
+    }
+}
+
+/// Run a shell command attached to a pseudo-terminal instead of pipes, so
+/// TTY-sensitive tools (colorized output, interactive progress bars) behave
+/// as they would on a real terminal. Combined stdout+stderr is captured
+/// from the pty master side (they're not distinguishable once merged onto
+/// one terminal, so `CommandResult::stderr` is always empty and the
+/// combined stream lands in `stdout`). Unix only: the function is not
+/// compiled on other platforms, since Windows has no pty equivalent wired
+/// up here.
+#[cfg(unix)]
+pub fn run_command_pty(cmd: &str) -> Result<CommandResult> {
+    use nix::pty::openpty;
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::unix::process::CommandExt;
+
+    let pty = openpty(None, None).context("Failed to allocate pty")?;
+    let master = File::from(pty.master);
+    let slave_stdin = File::from(pty.slave);
+    let slave_stdout = slave_stdin.try_clone().context("Failed to dup pty slave")?;
+    let slave_stderr = slave_stdin.try_clone().context("Failed to dup pty slave")?;
+
+    let mut command = Command::new("sh");
+    command
+        .args(["-c", cmd])
+        .stdin(Stdio::from(slave_stdin))
+        .stdout(Stdio::from(slave_stdout))
+        .stderr(Stdio::from(slave_stderr));
+    unsafe {
+        command.pre_exec(|| {
+            // Become session leader so the pty can act as our controlling
+            // terminal, the same way a real shell would attach to one.
+            if libc::setsid() < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+
+    let mut child = command.spawn().with_context(|| format!("Failed to spawn command: {}", cmd))?;
+    // Drop our copies of the slave end; only the child should hold it open,
+    // so the master sees EOF once the child exits.
+    drop(command);
+
+    let mut output = String::new();
+    let mut master = master;
+    // A pty master read after the slave closes returns EIO on Linux rather
+    // than a clean EOF; treat it the same as EOF.
+    loop {
+        let mut chunk = [0u8; 4096];
+        match master.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => output.push_str(&String::from_utf8_lossy(&chunk[..n])),
+            Err(e) if e.raw_os_error() == Some(libc::EIO) => break,
+            Err(e) => return Err(e).context("Failed to read from pty"),
+        }
+    }
+
+    let status = child.wait().context("Failed to wait for command")?;
+    Ok(CommandResult {
+        exit_code: status.code(),
+        stdout: output,
+        stderr: String::new(),
+        success: status.success(),
+        timed_out: false,
+        resource_limited: false,
+    })
+}
+
+/// Check if a command exists in PATH.
+pub fn command_exists(cmd: &str) -> bool {
+    if cfg!(target_os = "windows") {
+        Command::new("where").arg(cmd).output().is_ok_and(|o| o.status.success())
+    } else {
+        Command::new("which").arg(cmd).output().is_ok_and(|o| o.status.success())
+    }
+}
+
+/// Run git command and return output.
+pub fn git(args: &str) -> Result<CommandResult> {
+    run_command(&format!("git {}", args))
+}
+
+/// Run gh (GitHub CLI) command and return output.
+pub fn gh(args: &str) -> Result<CommandResult> {
+    run_command(&format!("gh {}", args))
+}
+
+/// Default timeout for a single plugin RPC call.
+const DEFAULT_PLUGIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// One line read from a plugin's stdout, or the reason reading stopped.
+enum PluginLine {
+    Line(String),
+    /// The child closed its stdout (EOF).
+    Closed,
+    /// The pipe errored out.
+    Error(String),
+}
+
+/// A long-lived external "minion" plugin speaking line-delimited JSON-RPC 2.0
+/// over stdin/stdout.
+///
+/// Hooks that used to hardcode a specific external tool (e.g. `copilot` for
+/// verification, `ruff`/`ty` for linting) can instead spawn a `Plugin` and
+/// drive it through [`Plugin::call`], letting users swap in their own agent
+/// without recompiling this crate.
+///
+/// Requests and responses are matched by `id`: each call blocks until the
+/// reader thread delivers the next response line, a malformed line arrives,
+/// or the timeout elapses; a response line whose `id` is not the request's
+/// is an error.
+pub struct Plugin {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: std::sync::mpsc::Receiver<PluginLine>,
+    next_id: AtomicI64,
+    timeout: Duration,
+}
+
+impl Plugin {
+    /// Spawn a plugin process at `path`, piping its stdin/stdout.
+    pub fn spawn(path: &str) -> Result<Self> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to spawn plugin: {}", path))?;
+
+        let stdin = child.stdin.take().context("Plugin has no stdin pipe")?;
+        let stdout = child.stdout.take().context("Plugin has no stdout pipe")?;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            loop {
+                let mut buf = String::new();
+                let sent = match reader.read_line(&mut buf) {
+                    Ok(0) => tx.send(PluginLine::Closed),
+                    Ok(_) => tx.send(PluginLine::Line(buf)),
+                    Err(e) => tx.send(PluginLine::Error(e.to_string())),
+                };
+                if sent.is_err() {
+                    // Plugin was dropped; nothing left to deliver to.
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin: Some(stdin),
+            lines: rx,
+            next_id: AtomicI64::new(1),
+            timeout: DEFAULT_PLUGIN_TIMEOUT,
+        })
+    }
+
+    /// Override the default per-call timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Call `method` with `params`, returning the JSON-RPC `result` field.
+    ///
+    /// Sends one JSON-RPC request per line and blocks until the next
+    /// response line arrives, a malformed line is read, or the timeout
+    /// elapses (in which case the child is killed and an error is returned
+    /// rather than hanging forever). A response whose `id` differs from the
+    /// request's is returned as an error.
+    pub fn call(&mut self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let stdin = self.stdin.as_mut().context("Plugin stdin already closed")?;
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+        stdin
+            .write_all(line.as_bytes())
+            .context("Failed to write plugin request")?;
+        stdin.flush().context("Failed to flush plugin stdin")?;
+
+        let response_line = match self.lines.recv_timeout(self.timeout) {
+            Ok(PluginLine::Line(line)) => line,
+            Ok(PluginLine::Closed) => {
+                let _ = self.child.kill();
+                anyhow::bail!("Plugin closed its stdout before responding to '{}'", method);
+            }
+            Ok(PluginLine::Error(e)) => {
+                let _ = self.child.kill();
+                anyhow::bail!("Failed to read plugin response: {}", e);
+            }
+            Err(_) => {
+                let _ = self.child.kill();
+                anyhow::bail!(
+                    "Plugin call '{}' timed out after {:?}",
+                    method,
+                    self.timeout
+                );
+            }
+        };
+
+        let response: serde_json::Value = serde_json::from_str(response_line.trim())
+            .with_context(|| format!("Plugin returned non-JSON line: {}", response_line.trim()))?;
+
+        let response_id = response.get("id").and_then(|v| v.as_i64());
+        if response_id != Some(id) {
+            anyhow::bail!(
+                "Plugin response id mismatch: expected {}, got {:?}",
+                id,
+                response_id
+            );
+        }
+
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("Plugin returned error for '{}': {}", method, error);
+        }
+
+        Ok(response.get("result").cloned().unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Close stdin and wait for the plugin to exit.
+    pub fn shutdown(mut self) -> Result<()> {
+        drop(self.stdin.take());
+        self.child.wait().context("Failed to wait for plugin exit")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_command_success() {
+        let result = run_command("echo hello").unwrap();
+        assert!(result.success);
+        assert!(result.stdout.trim() == "hello");
+    }
+
+    #[test]
+    fn test_run_command_failure() {
+        let result = run_command("exit 1").unwrap();
+        assert!(!result.success);
+        assert_eq!(result.exit_code, Some(1));
+    }
+
+    #[test]
+    fn test_command_exists() {
+        // 'echo' should exist on all platforms
+        assert!(command_exists("echo"));
+        // Random string should not exist
+        assert!(!command_exists("nonexistent_command_12345"));
+    }
+
+    #[test]
+    fn test_git_status() {
+        // This test assumes we're in a git repo
+        let result = git("status --porcelain");
+        assert!(result.is_ok());
+    }
+
+    /// Write an executable shell script that echoes back each JSON-RPC
+    /// request's `id` wrapped in `{"jsonrpc":"2.0","id":<id>,"result":"ok"}`.
+    fn echo_plugin_script() -> (tempfile::TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("echo-plugin.sh");
+        std::fs::write(
+            &path,
+            r#"#!/bin/sh
+while IFS= read -r line; do
+  id=$(echo "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":\"ok\"}"
+done
+"#,
+        )
+        .unwrap();
+        let mut perms = std::fs::metadata(&path).unwrap().permissions();
+        std::os::unix::fs::PermissionsExt::set_mode(&mut perms, 0o755);
+        std::fs::set_permissions(&path, perms).unwrap();
+        (dir, path.to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_plugin_call_roundtrip() {
+        let (_dir, script) = echo_plugin_script();
+        let mut plugin = Plugin::spawn(&script).unwrap();
+
+        let result = plugin.call("ping", serde_json::json!({})).unwrap();
+        assert_eq!(result, serde_json::json!("ok"));
+
+        plugin.shutdown().unwrap();
+    }
+
+    #[test]
+    fn test_plugin_call_times_out_on_dead_pipe() {
+        let (_dir, _script) = echo_plugin_script();
+        // `true` exits immediately without ever writing a response line,
+        // simulating a dead/unresponsive child.
+        let mut plugin = Plugin::spawn("true")
+            .unwrap()
+            .with_timeout(Duration::from_millis(200));
+
+        let result = plugin.call("ping", serde_json::json!({}));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_run_command_with_timeout_completes_within_budget() {
+        let result = run_command_with_timeout("echo hi", Duration::from_secs(5)).unwrap();
+        assert!(!result.timed_out);
+        assert!(result.success);
+        assert_eq!(result.stdout.trim(), "hi");
+    }
+
+    #[test]
+    fn test_run_command_with_timeout_marks_timed_out() {
+        let result =
+            run_command_with_timeout("sleep 5", Duration::from_millis(100)).unwrap();
+        assert!(result.timed_out);
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn test_run_command_with_timeout_survives_large_output() {
+        // More than a typical 64KB pipe buffer on both streams; without
+        // concurrent draining this would deadlock (child blocks writing,
+        // we're only polling try_wait) and fire a false timeout.
+        let result = run_command_with_timeout(
+            "yes line | head -c 200000 >&1; yes line | head -c 200000 >&2",
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert!(!result.timed_out);
+        assert!(result.stdout.len() > 100_000);
+        assert!(result.stderr.len() > 100_000);
+    }
+
+    #[test]
+    fn test_run_command_with_timeout_streaming_calls_on_line() {
+        let lines = std::sync::Mutex::new(Vec::new());
+        let result = run_command_with_timeout_streaming(
+            "echo one; echo two 1>&2",
+            Duration::from_secs(5),
+            |kind, text| lines.lock().unwrap().push((kind, text.to_string())),
+        )
+        .unwrap();
+        assert!(!result.timed_out);
+        let lines = lines.into_inner().unwrap();
+        assert!(lines.contains(&(StreamKind::Stdout, "one".to_string())));
+        assert!(lines.contains(&(StreamKind::Stderr, "two".to_string())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_with_timeout_kills_grandchildren() {
+        // The marker file is only removed by the background grandchild once
+        // its own 1s `sleep` finishes, so if it is gone well after that, the
+        // grandchild survived its parent being killed -- i.e. the process
+        // group wasn't terminated.
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("alive");
+        std::fs::write(&marker, b"").unwrap();
+        let cmd = format!(
+            "(sleep 1; rm -f {marker}) & sleep 5",
+            marker = marker.display()
+        );
+
+        let result = run_command_with_timeout(&cmd, Duration::from_millis(200)).unwrap();
+        assert!(result.timed_out);
+
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(marker.exists(), "grandchild should have been killed before it could remove the marker");
+    }
+
+    #[test]
+    fn test_run_command_sandboxed_success() {
+        let result = run_command_sandboxed("echo hi", Limits::default()).unwrap();
+        assert!(result.success);
+        assert!(!result.resource_limited);
+        assert!(!result.timed_out);
+        assert_eq!(result.stdout.trim(), "hi");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_sandboxed_enforces_cpu_limit() {
+        let limits = Limits::new().cpu_seconds(1).timeout(Duration::from_secs(10));
+        // Busy-loop that would otherwise run well past the CPU budget.
+        let result = run_command_sandboxed(":; while true; do :; done", limits).unwrap();
+        assert!(!result.timed_out);
+        assert!(result.resource_limited);
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn test_run_command_sandboxed_timeout_path_still_works() {
+        let limits = Limits::new().timeout(Duration::from_millis(100));
+        let result = run_command_sandboxed("sleep 5", limits).unwrap();
+        assert!(result.timed_out);
+        assert!(!result.resource_limited);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_pty_attaches_terminal() {
+        // `test -t 0` only succeeds when stdin is a tty; over a plain pipe
+        // (as `run_command` uses) this would be false.
+        let result = run_command_pty("test -t 0 && echo is_a_tty").unwrap();
+        assert!(result.success);
+        assert!(result.stdout.contains("is_a_tty"));
+    }
+}
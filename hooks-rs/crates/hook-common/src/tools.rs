@@ -0,0 +1,285 @@
+//! Per-tool extension point for the memory recall/record hooks.
+//!
+//! Previously each hook had its own hardcoded `match tool_name { ... }`
+//! block to build a recall query or a record summary. That meant adding
+//! support for a new tool meant editing core hook source. Instead, each
+//! tool's behavior is a [`ToolHandler`] registered into a
+//! [`ToolHandlerRegistry`] keyed by tool name — the built-in Bash/Edit/
+//! Write/Task/WebFetch/WebSearch tools ship as handlers here, and callers
+//! (including third parties) can register additional ones without
+//! touching the hooks themselves.
+
+use crate::input::ToolInput;
+use std::collections::HashMap;
+
+/// Per-tool behavior consumed by the recall and record hooks.
+pub trait ToolHandler: Send + Sync {
+    /// Build a memory-recall search query from this tool's input, or
+    /// `None` to fall back to the tool name itself.
+    fn query(&self, input: &ToolInput) -> Option<String>;
+
+    /// Summarize this tool's input/output for a memory record, or `None`
+    /// to fall back to a generic "<tool> execution" summary.
+    fn summary(&self, input: &ToolInput, output: &str) -> Option<String>;
+
+    /// Whether this tool participates in memory recall by default.
+    fn recalls(&self) -> bool;
+
+    /// Whether this tool participates in memory recording by default.
+    fn records(&self) -> bool;
+}
+
+/// Tool name -> [`ToolHandler`] dispatch table.
+pub struct ToolHandlerRegistry {
+    handlers: HashMap<String, Box<dyn ToolHandler>>,
+}
+
+impl Default for ToolHandlerRegistry {
+    fn default() -> Self {
+        let mut registry = Self { handlers: HashMap::new() };
+        registry.register("Bash", Box::new(BashHandler));
+        registry.register("Edit", Box::new(EditHandler));
+        registry.register("Write", Box::new(WriteHandler));
+        registry.register("Task", Box::new(TaskHandler));
+        registry.register("WebFetch", Box::new(WebFetchHandler));
+        registry.register("WebSearch", Box::new(WebSearchHandler));
+        registry
+    }
+}
+
+impl ToolHandlerRegistry {
+    /// Register (or replace) the handler for `tool_name`.
+    pub fn register(&mut self, tool_name: &str, handler: Box<dyn ToolHandler>) {
+        self.handlers.insert(tool_name.to_string(), handler);
+    }
+
+    /// The handler registered for `tool_name`, if any.
+    pub fn get(&self, tool_name: &str) -> Option<&dyn ToolHandler> {
+        self.handlers.get(tool_name).map(|h| h.as_ref())
+    }
+
+    /// Names of all registered tools that recall by default.
+    pub fn recall_tools(&self) -> Vec<String> {
+        self.handlers
+            .iter()
+            .filter(|(_, handler)| handler.recalls())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Names of all registered tools that record by default.
+    pub fn record_tools(&self) -> Vec<String> {
+        self.handlers
+            .iter()
+            .filter(|(_, handler)| handler.records())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+struct BashHandler;
+
+impl ToolHandler for BashHandler {
+    fn query(&self, input: &ToolInput) -> Option<String> {
+        let command = input.command.as_deref().unwrap_or("");
+        let first_line = command.lines().next().unwrap_or("");
+        let cmd_name = first_line.split_whitespace().next().unwrap_or("");
+        Some(format!("command {}", cmd_name))
+    }
+
+    fn summary(&self, input: &ToolInput, output: &str) -> Option<String> {
+        let command = input.command.as_deref().unwrap_or("");
+        let first_line = command.lines().next().unwrap_or("");
+        let truncated = if first_line.len() > 100 { &first_line[..100] } else { first_line };
+        let success =
+            !output.to_lowercase().contains("error") && !output.to_lowercase().contains("failed");
+        Some(format!("Command: {} -> {}", truncated, if success { "Success" } else { "Failed" }))
+    }
+
+    fn recalls(&self) -> bool {
+        true
+    }
+
+    fn records(&self) -> bool {
+        true
+    }
+}
+
+struct EditHandler;
+
+impl ToolHandler for EditHandler {
+    fn query(&self, input: &ToolInput) -> Option<String> {
+        let file_path = input.file_path.as_deref().unwrap_or("");
+        let filename = file_path.rsplit('/').next().unwrap_or("");
+        Some(format!("edit {}", filename))
+    }
+
+    fn summary(&self, input: &ToolInput, _output: &str) -> Option<String> {
+        let file_path = input.file_path.as_deref().unwrap_or("unknown");
+        let filename = file_path.rsplit('/').next().unwrap_or(file_path);
+        Some(format!("Edited: {}", filename))
+    }
+
+    fn recalls(&self) -> bool {
+        true
+    }
+
+    fn records(&self) -> bool {
+        true
+    }
+}
+
+struct WriteHandler;
+
+impl ToolHandler for WriteHandler {
+    fn query(&self, input: &ToolInput) -> Option<String> {
+        let file_path = input.file_path.as_deref().unwrap_or("");
+        let filename = file_path.rsplit('/').next().unwrap_or("");
+        Some(format!("create {}", filename))
+    }
+
+    fn summary(&self, input: &ToolInput, _output: &str) -> Option<String> {
+        let file_path = input.file_path.as_deref().unwrap_or("unknown");
+        let filename = file_path.rsplit('/').next().unwrap_or(file_path);
+        Some(format!("Created: {}", filename))
+    }
+
+    fn recalls(&self) -> bool {
+        true
+    }
+
+    fn records(&self) -> bool {
+        true
+    }
+}
+
+struct TaskHandler;
+
+impl ToolHandler for TaskHandler {
+    fn query(&self, input: &ToolInput) -> Option<String> {
+        let prompt = input.prompt.as_deref().unwrap_or("");
+        Some(prompt.chars().take(200).collect())
+    }
+
+    fn summary(&self, input: &ToolInput, _output: &str) -> Option<String> {
+        let prompt = input.prompt.as_deref().unwrap_or("");
+        let truncated: String = prompt.chars().take(100).collect();
+        let subagent = input.subagent_type.as_deref().unwrap_or("unknown");
+        Some(format!("Task ({}): {}", subagent, truncated))
+    }
+
+    fn recalls(&self) -> bool {
+        true
+    }
+
+    fn records(&self) -> bool {
+        true
+    }
+}
+
+struct WebFetchHandler;
+
+impl ToolHandler for WebFetchHandler {
+    fn query(&self, input: &ToolInput) -> Option<String> {
+        let url = input.extra.get("url").and_then(|v| v.as_str()).unwrap_or("");
+        Some(format!("fetch {}", url))
+    }
+
+    fn summary(&self, input: &ToolInput, _output: &str) -> Option<String> {
+        let url = input.extra.get("url").and_then(|v| v.as_str()).unwrap_or("unknown");
+        Some(format!("Fetched: {}", url))
+    }
+
+    fn recalls(&self) -> bool {
+        true
+    }
+
+    fn records(&self) -> bool {
+        true
+    }
+}
+
+struct WebSearchHandler;
+
+impl ToolHandler for WebSearchHandler {
+    fn query(&self, input: &ToolInput) -> Option<String> {
+        Some(input.extra.get("query").and_then(|v| v.as_str()).unwrap_or("").to_string())
+    }
+
+    fn summary(&self, input: &ToolInput, _output: &str) -> Option<String> {
+        let query = input.extra.get("query").and_then(|v| v.as_str()).unwrap_or("");
+        Some(format!("Searched: {}", query))
+    }
+
+    fn recalls(&self) -> bool {
+        true
+    }
+
+    fn records(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_registry_covers_built_ins() {
+        let registry = ToolHandlerRegistry::default();
+        assert!(registry.get("Bash").is_some());
+        assert!(registry.get("Edit").is_some());
+        assert!(registry.get("WebSearch").is_some());
+        assert!(registry.get("Read").is_none());
+    }
+
+    #[test]
+    fn test_recall_and_record_tools_include_built_ins() {
+        let registry = ToolHandlerRegistry::default();
+        assert!(registry.recall_tools().iter().any(|t| t == "Bash"));
+        assert!(registry.record_tools().iter().any(|t| t == "Task"));
+    }
+
+    #[test]
+    fn test_bash_handler_query_and_summary() {
+        let handler = BashHandler;
+        let input = ToolInput {
+            command: Some("cargo test --workspace".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(handler.query(&input), Some("command cargo".to_string()));
+        assert_eq!(
+            handler.summary(&input, "ok"),
+            Some("Command: cargo test --workspace -> Success".to_string())
+        );
+    }
+
+    #[test]
+    fn test_register_custom_handler() {
+        struct CustomHandler;
+        impl ToolHandler for CustomHandler {
+            fn query(&self, _input: &ToolInput) -> Option<String> {
+                Some("custom query".to_string())
+            }
+            fn summary(&self, _input: &ToolInput, _output: &str) -> Option<String> {
+                Some("custom summary".to_string())
+            }
+            fn recalls(&self) -> bool {
+                true
+            }
+            fn records(&self) -> bool {
+                false
+            }
+        }
+
+        let mut registry = ToolHandlerRegistry::default();
+        registry.register("CustomTool", Box::new(CustomHandler));
+
+        assert!(registry.recall_tools().iter().any(|t| t == "CustomTool"));
+        assert!(!registry.record_tools().iter().any(|t| t == "CustomTool"));
+        assert_eq!(
+            registry.get("CustomTool").unwrap().query(&ToolInput::default()),
+            Some("custom query".to_string())
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Shared, short-TTL cache of VCS state to avoid redundant `git`/`gh`
+//! subprocess spawns.
+//!
+//! `ensure-pr-open` and `auto-create-pr` each shell out to `gh pr list`
+//! and `git status --porcelain` independently, and a session that fires
+//! many PreToolUse/PostToolUse hooks ends up re-deriving the same answer
+//! over and over. Taking the spirit of Mercurial's "compute shared state
+//! once" optimization, this memoizes the two expensive queries --
+//! open-PR branches and working-tree dirtiness -- to a small JSON file
+//! under `.claude/cache/`, keyed on the repo's current HEAD short hash
+//! plus its `.git/index` mtime: either changing invalidates the cache
+//! immediately, and a cached value also expires after [`TTL_SECONDS`]
+//! regardless (a PR can be opened/closed without a local commit).
+//!
+//! [`has_any_open_pr`], [`has_open_pr`], and [`has_uncommitted_changes`]
+//! are the only entry points hooks should call; they transparently
+//! refresh the cache on a miss.
+
+use crate::git;
+use crate::subprocess::{gh, git as git_cli};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A cached value is reused for at most this long, even if the repo's
+/// HEAD/index haven't changed (PR state can change without a local commit).
+const TTL_SECONDS: u64 = 10;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CacheEntry {
+    head: String,
+    index_mtime: u64,
+    cached_at: u64,
+    #[serde(default)]
+    dirty: Option<bool>,
+    #[serde(default)]
+    open_pr_branches: Option<Vec<String>>,
+}
+
+/// Default cache file: `<project_dir>/.claude/cache/vcs-state.json`.
+pub fn default_path(project_dir: &Path) -> PathBuf {
+    project_dir.join(".claude").join("cache").join("vcs-state.json")
+}
+
+/// Whether the working tree has any uncommitted changes (`git status
+/// --porcelain` is non-empty).
+pub fn has_uncommitted_changes(project_dir: &str) -> bool {
+    let (path, mut entry) = fresh_entry(project_dir);
+    if let Some(dirty) = entry.dirty {
+        return dirty;
+    }
+
+    let dirty = compute_dirty();
+    entry.dirty = Some(dirty);
+    write_cache(&path, &entry);
+    dirty
+}
+
+/// Whether any PR is currently open against this repository.
+pub fn has_any_open_pr(project_dir: &str) -> bool {
+    !open_pr_branches(project_dir).is_empty()
+}
+
+/// Whether `branch` specifically has an open PR.
+pub fn has_open_pr(project_dir: &str, branch: &str) -> bool {
+    open_pr_branches(project_dir).iter().any(|b| b == branch)
+}
+
+fn open_pr_branches(project_dir: &str) -> Vec<String> {
+    let (path, mut entry) = fresh_entry(project_dir);
+    if let Some(branches) = &entry.open_pr_branches {
+        return branches.clone();
+    }
+
+    let branches = compute_open_pr_branches();
+    entry.open_pr_branches = Some(branches.clone());
+    write_cache(&path, &entry);
+    branches
+}
+
+/// Load the cache entry for `project_dir` if it still matches the repo's
+/// current HEAD+index and hasn't expired; otherwise return a fresh
+/// (all-fields-unset) entry keyed to the current state, dropping any
+/// stale cached values.
+fn fresh_entry(project_dir: &str) -> (PathBuf, CacheEntry) {
+    let path = default_path(Path::new(project_dir));
+    let now = now_secs();
+    let Some((head, index_mtime)) = current_key(project_dir) else {
+        return (path, CacheEntry { cached_at: now, ..Default::default() });
+    };
+
+    if let Some(entry) = read_cache(&path) {
+        if entry.head == head && entry.index_mtime == index_mtime && now.saturating_sub(entry.cached_at) < TTL_SECONDS {
+            return (path, entry);
+        }
+    }
+
+    (path, CacheEntry { head, index_mtime, cached_at: now, dirty: None, open_pr_branches: None })
+}
+
+/// The repo's current HEAD short hash and its `.git/index` mtime (as a
+/// unix timestamp), used together as the cache invalidation key.
+fn current_key(project_dir: &str) -> Option<(String, u64)> {
+    let repo = git::open(project_dir).ok()?;
+    let head = git::current_short_hash(&repo).ok()?;
+    let index_mtime = std::fs::metadata(repo.git_dir().join("index"))
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Some((head, index_mtime))
+}
+
+fn compute_dirty() -> bool {
+    match git_cli("status --porcelain") {
+        Ok(result) => result.success && !result.stdout.trim().is_empty(),
+        Err(_) => false,
+    }
+}
+
+fn compute_open_pr_branches() -> Vec<String> {
+    match gh("pr list --state open --json headRefName") {
+        Ok(result) if result.success => serde_json::from_str::<Vec<serde_json::Value>>(&result.stdout)
+            .map(|prs| {
+                prs.iter()
+                    .filter_map(|pr| pr.get("headRefName")?.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn read_cache(path: &Path) -> Option<CacheEntry> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_cache(path: &Path, entry: &CacheEntry) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(entry) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_entry_missing_repo_has_no_key() {
+        let (_path, entry) = fresh_entry("/nonexistent/not-a-repo");
+        assert!(entry.head.is_empty());
+        assert!(entry.dirty.is_none());
+    }
+
+    #[test]
+    fn test_cache_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vcs-state.json");
+        let entry = CacheEntry {
+            head: "abc1234".to_string(),
+            index_mtime: 42,
+            cached_at: 100,
+            dirty: Some(true),
+            open_pr_branches: Some(vec!["feature/x".to_string()]),
+        };
+
+        write_cache(&path, &entry);
+        let loaded = read_cache(&path).unwrap();
+        assert_eq!(loaded.head, "abc1234");
+        assert_eq!(loaded.dirty, Some(true));
+        assert_eq!(loaded.open_pr_branches, Some(vec!["feature/x".to_string()]));
+    }
+
+    #[test]
+    fn test_expired_entry_is_not_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vcs-state.json");
+        let stale = CacheEntry {
+            head: "abc1234".to_string(),
+            index_mtime: 42,
+            cached_at: 0,
+            dirty: Some(true),
+            open_pr_branches: None,
+        };
+        write_cache(&path, &stale);
+
+        let now = now_secs();
+        let reused = read_cache(&path)
+            .filter(|e| e.head == "abc1234" && e.index_mtime == 42 && now.saturating_sub(e.cached_at) < TTL_SECONDS);
+        assert!(reused.is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! Bulk memory ingestion: walk a project tree to seed `MemoryStorage`.
+//!
+//! The auto-learn hook only grows memory one event at a time, from user
+//! prompts. `Crawl` lets a hook pre-populate project-scoped memory (file
+//! layout, conventions) by walking the tree once and handing eligible files
+//! to a caller-supplied callback that decides what `MemoryEvent`s to emit.
+
+use anyhow::{bail, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+
+/// Source file extensions eligible for crawling by default.
+const DEFAULT_EXTENSIONS: &[&str] = &["py", "rs", "ts", "tsx", "js", "jsx", "go", "md"];
+
+/// Configuration for a `Crawl`.
+#[derive(Debug, Clone)]
+pub struct CrawlConfig {
+    /// If true, visit every file regardless of extension and never
+    /// short-circuit on a previously seen extension.
+    pub all_files: bool,
+    /// Extensions eligible for crawling (ignored when `all_files` is set).
+    pub extensions: Vec<String>,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        Self {
+            all_files: false,
+            extensions: DEFAULT_EXTENSIONS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Walks a project tree, honoring `.gitignore`/`.ignore` and hidden-file
+/// rules, and dedupes work by tracking already-crawled file extensions.
+pub struct Crawl {
+    config: CrawlConfig,
+    root: Utf8PathBuf,
+    seen_extensions: HashSet<String>,
+}
+
+impl Crawl {
+    /// Create a crawler rooted at `root`.
+    ///
+    /// Rejects roots that aren't plain local paths (mirroring a
+    /// `file://`-only guard): no URL scheme separators allowed.
+    pub fn new(config: CrawlConfig, root: impl AsRef<Utf8Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        if root.as_str().contains("://") {
+            bail!("Crawl root must be a plain local path, got: {}", root);
+        }
+
+        Ok(Self {
+            config,
+            root,
+            seen_extensions: HashSet::new(),
+        })
+    }
+
+    /// Crawl the tree, unless `triggered_file`'s extension was already
+    /// crawled in a prior call, in which case the walk is short-circuited.
+    ///
+    /// `f` is invoked once per eligible file in walk order; its `Err` aborts
+    /// the crawl.
+    pub fn maybe_do_crawl(
+        &mut self,
+        triggered_file: Option<&Utf8Path>,
+        mut f: impl FnMut(&Utf8Path) -> Result<()>,
+    ) -> Result<()> {
+        if !self.config.all_files {
+            if let Some(triggered) = triggered_file {
+                if let Some(ext) = extension_of(triggered) {
+                    if self.seen_extensions.contains(&ext) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        let mut walked_extensions = HashSet::new();
+
+        for entry in WalkBuilder::new(&self.root).hidden(true).build() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(_) => continue,
+            };
+
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+
+            let path = match Utf8PathBuf::from_path_buf(entry.into_path()) {
+                Ok(path) => path,
+                Err(_) => continue, // skip non-UTF-8 paths
+            };
+
+            if !self.config.all_files {
+                let Some(ext) = extension_of(&path) else {
+                    continue;
+                };
+                if !self.config.extensions.iter().any(|e| e == &ext) {
+                    continue;
+                }
+            }
+
+            f(&path)?;
+
+            if let Some(ext) = extension_of(&path) {
+                walked_extensions.insert(ext);
+            }
+        }
+
+        self.seen_extensions.extend(walked_extensions);
+
+        Ok(())
+    }
+}
+
+fn extension_of(path: &Utf8Path) -> Option<String> {
+    path.extension().map(|e| e.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rejects_url_roots() {
+        let config = CrawlConfig::default();
+        let result = Crawl::new(config, Utf8PathBuf::from("file:///tmp/project"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_crawls_eligible_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join("b.py"), "pass").unwrap();
+        fs::write(dir.path().join("c.bin"), [0u8, 1, 2]).unwrap();
+
+        let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+        let mut crawl = Crawl::new(CrawlConfig::default(), root).unwrap();
+
+        let mut visited = Vec::new();
+        crawl
+            .maybe_do_crawl(None, |path| {
+                visited.push(path.to_string());
+                Ok(())
+            })
+            .unwrap();
+
+        assert!(visited.iter().any(|p| p.ends_with("a.rs")));
+        assert!(visited.iter().any(|p| p.ends_with("b.py")));
+        assert!(!visited.iter().any(|p| p.ends_with("c.bin")));
+    }
+
+    #[test]
+    fn test_crawls_every_file_sharing_an_extension_in_one_walk() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        fs::write(dir.path().join("b.rs"), "fn b() {}").unwrap();
+        fs::write(dir.path().join("c.rs"), "fn c() {}").unwrap();
+
+        let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+        let mut crawl = Crawl::new(CrawlConfig::default(), root).unwrap();
+
+        let mut visited = Vec::new();
+        crawl
+            .maybe_do_crawl(None, |path| {
+                visited.push(path.to_string());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(visited.len(), 3);
+        assert!(visited.iter().any(|p| p.ends_with("a.rs")));
+        assert!(visited.iter().any(|p| p.ends_with("b.rs")));
+        assert!(visited.iter().any(|p| p.ends_with("c.rs")));
+    }
+
+    #[test]
+    fn test_short_circuits_on_seen_extension() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.rs"), "fn main() {}").unwrap();
+
+        let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+        let mut crawl = Crawl::new(CrawlConfig::default(), root).unwrap();
+
+        let mut count = 0;
+        crawl
+            .maybe_do_crawl(None, |_| {
+                count += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Second crawl triggered by a file whose extension was already
+        // seen should short-circuit without visiting anything.
+        let mut second_count = 0;
+        crawl
+            .maybe_do_crawl(Some(Utf8Path::new("other.rs")), |_| {
+                second_count += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(second_count, 0);
+    }
+}
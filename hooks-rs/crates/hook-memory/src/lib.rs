@@ -4,9 +4,14 @@
 //! - Memory event schema
 //! - JSONL storage
 //! - Basic scoring
+//! - Bulk ingestion by crawling a project tree
 
+pub mod crawl;
+pub mod rank;
 pub mod schema;
 pub mod storage;
 
+pub use crawl::{Crawl, CrawlConfig};
+pub use rank::{rank_memories, rank_memories_with_half_life, RankedMemory};
 pub use schema::{AgentType, MemoryEvent, MemoryScope, MemoryType};
 pub use storage::MemoryStorage;
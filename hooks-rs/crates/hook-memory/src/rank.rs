@@ -0,0 +1,275 @@
+//! Relevance ranking over stored `MemoryEvent`s: BM25 similarity, recency,
+//! confidence, and tag matches combined into one weighted score.
+//!
+//! Cheap and dependency-free: no embeddings, just classic BM25 term
+//! statistics over the corpus (squashed to a similarity-like `[0, 1)`
+//! range), combined with exponential recency decay, the event's own
+//! `confidence`, and a flat bonus when a query token matches a tag.
+
+use crate::schema::MemoryEvent;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// BM25 term-frequency saturation parameter.
+const K1: f64 = 1.2;
+/// BM25 length-normalization parameter.
+const B: f64 = 0.75;
+/// Default recency half-life in days: a memory's recency contribution is
+/// `exp(-age_days / DEFAULT_HALF_LIFE_DAYS)`, so it shrinks by a factor of
+/// e over each `DEFAULT_HALF_LIFE_DAYS`.
+const DEFAULT_HALF_LIFE_DAYS: f64 = 14.0;
+/// Weight on the BM25 similarity term.
+const W_SIM: f64 = 0.6;
+/// Weight on the recency term.
+const W_REC: f64 = 0.25;
+/// Weight on the event's own confidence.
+const W_CONF: f64 = 0.15;
+/// Flat bonus added when a query token matches one of the event's tags.
+const TAG_MATCH_BONUS: f64 = 0.1;
+/// Scores at or below this floor are dropped rather than injected.
+const SCORE_FLOOR: f64 = 0.05;
+
+/// Tokenize on Unicode word boundaries (alphanumeric runs), lowercased.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        } else if !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn term_counts(tokens: &[String]) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for t in tokens {
+        *counts.entry(t.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Age of an event in days, relative to `now`. Unparseable timestamps are
+/// treated as age 0, i.e. given the full recency weight of a memory
+/// created at `now`.
+fn age_days(event: &MemoryEvent, now: DateTime<Utc>) -> f64 {
+    event.age_days(now).unwrap_or(0.0).max(0.0)
+}
+
+/// A memory paired with its relevance score, returned in descending order.
+#[derive(Debug, Clone)]
+pub struct RankedMemory {
+    pub event: MemoryEvent,
+    pub score: f64,
+}
+
+/// Rank `events` against `query` using the default recency half-life,
+/// returning the top `limit` above the score floor. An empty corpus or
+/// query yields no results.
+pub fn rank_memories(events: &[MemoryEvent], query: &str, limit: usize) -> Vec<RankedMemory> {
+    rank_memories_with_half_life(events, query, limit, DEFAULT_HALF_LIFE_DAYS)
+}
+
+/// Same as [`rank_memories`] but with a configurable recency half-life (in
+/// days): a memory's recency contribution is `exp(-age_days / half_life_days)`,
+/// shrinking by a factor of e over each `half_life_days`.
+pub fn rank_memories_with_half_life(
+    events: &[MemoryEvent],
+    query: &str,
+    limit: usize,
+    half_life_days: f64,
+) -> Vec<RankedMemory> {
+    rank_memories_at(events, query, limit, half_life_days, Utc::now())
+}
+
+/// Same as [`rank_memories_with_half_life`] but with an injectable `now`,
+/// for deterministic tests.
+fn rank_memories_at(
+    events: &[MemoryEvent],
+    query: &str,
+    limit: usize,
+    half_life_days: f64,
+    now: DateTime<Utc>,
+) -> Vec<RankedMemory> {
+    let query_terms = tokenize(query);
+    if events.is_empty() || query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    let doc_tokens: Vec<Vec<String>> = events.iter().map(|e| tokenize(&e.content)).collect();
+    let doc_counts: Vec<HashMap<&str, usize>> = doc_tokens.iter().map(|t| term_counts(t)).collect();
+
+    let n = events.len() as f64;
+    let avg_len = doc_tokens.iter().map(|t| t.len()).sum::<usize>() as f64 / n;
+
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for term in &query_terms {
+        let count = doc_counts
+            .iter()
+            .filter(|counts| counts.contains_key(term.as_str()))
+            .count();
+        df.insert(term.as_str(), count);
+    }
+
+    let mut ranked: Vec<RankedMemory> = events
+        .iter()
+        .zip(doc_tokens.iter())
+        .zip(doc_counts.iter())
+        .map(|((event, tokens), counts)| {
+            let len = tokens.len() as f64;
+            let mut bm25 = 0.0;
+            for term in &query_terms {
+                let df_t = *df.get(term.as_str()).unwrap_or(&0) as f64;
+                let idf = ((n - df_t + 0.5) / (df_t + 0.5) + 1.0).ln();
+                let f = *counts.get(term.as_str()).unwrap_or(&0) as f64;
+                if f == 0.0 {
+                    continue;
+                }
+                let numerator = f * (K1 + 1.0);
+                let denominator = f + K1 * (1.0 - B + B * len / avg_len);
+                bm25 += idf * (numerator / denominator);
+            }
+            // Squash the unbounded BM25 score into a cosine-sim-like
+            // [0, 1) range so it combines sanely with the other terms.
+            let sim = bm25 / (bm25 + 1.0);
+
+            let recency = (-age_days(event, now) / half_life_days).exp();
+
+            let mut score = W_SIM * sim + W_REC * recency + W_CONF * event.confidence;
+
+            if query_terms
+                .iter()
+                .any(|term| event.tags.iter().any(|tag| tag.to_lowercase() == *term))
+            {
+                score += TAG_MATCH_BONUS;
+            }
+
+            RankedMemory {
+                event: event.clone(),
+                score,
+            }
+        })
+        .filter(|ranked| ranked.score > SCORE_FLOOR)
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(limit);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{AgentType, MemoryScope, MemoryType};
+    use chrono::Duration;
+
+    fn event(content: &str, memory_type: MemoryType, created_at: DateTime<Utc>) -> MemoryEvent {
+        event_with(content, memory_type, created_at, 1.0, &[])
+    }
+
+    fn event_with(
+        content: &str,
+        memory_type: MemoryType,
+        created_at: DateTime<Utc>,
+        confidence: f64,
+        tags: &[&str],
+    ) -> MemoryEvent {
+        MemoryEvent {
+            id: "test".to_string(),
+            content: content.to_string(),
+            memory_type,
+            scope: MemoryScope::User,
+            source_agent: AgentType::System,
+            context: String::new(),
+            confidence,
+            ttl_days: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            metadata: HashMap::new(),
+            created_at: created_at.to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_empty_corpus_returns_nothing() {
+        let now = Utc::now();
+        assert!(rank_memories_at(&[], "test query", 5, DEFAULT_HALF_LIFE_DAYS, now).is_empty());
+    }
+
+    #[test]
+    fn test_ranks_relevant_memory_higher() {
+        let now = Utc::now();
+        let events = vec![
+            event("PRは日本語で書く", MemoryType::Preference, now),
+            event("今日は晴れです", MemoryType::Observation, now),
+        ];
+
+        let ranked = rank_memories_at(&events, "PRは日本語で書いて", 5, DEFAULT_HALF_LIFE_DAYS, now);
+        assert!(!ranked.is_empty());
+        assert!(ranked[0].event.content.contains("日本語"));
+    }
+
+    #[test]
+    fn test_recency_favors_fresh_memories() {
+        let now = Utc::now();
+        let stale = event("テストを先に書く", MemoryType::Workflow, now - Duration::days(365));
+        let fresh = event("テストを先に書く", MemoryType::Workflow, now);
+
+        let ranked = rank_memories_at(
+            &[stale.clone(), fresh.clone()],
+            "テストを先に書く",
+            5,
+            DEFAULT_HALF_LIFE_DAYS,
+            now,
+        );
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked[0].score > ranked[1].score);
+    }
+
+    #[test]
+    fn test_confidence_breaks_ties_between_equally_relevant_memories() {
+        let now = Utc::now();
+        let confident = event_with("毎回テストを先に書く", MemoryType::Workflow, now, 1.0, &[]);
+        let unsure = event_with("毎回テストを先に書く", MemoryType::Workflow, now, 0.2, &[]);
+
+        let ranked = rank_memories_at(
+            &[unsure, confident.clone()],
+            "毎回テストを先に書く",
+            5,
+            DEFAULT_HALF_LIFE_DAYS,
+            now,
+        );
+        assert_eq!(ranked[0].event.confidence, confident.confidence);
+    }
+
+    #[test]
+    fn test_tag_match_bonus_surfaces_matching_memory() {
+        let now = Utc::now();
+        let tagged = event_with("予定", MemoryType::Preference, now, 1.0, &["pr"]);
+        let untagged = event_with("予定", MemoryType::Preference, now, 1.0, &[]);
+
+        let ranked = rank_memories_at(
+            &[untagged, tagged.clone()],
+            "pr",
+            5,
+            DEFAULT_HALF_LIFE_DAYS,
+            now,
+        );
+        assert_eq!(ranked[0].event.tags, tagged.tags);
+    }
+
+    #[test]
+    fn test_half_life_controls_recency_decay_rate() {
+        let now = Utc::now();
+        let a = event("テストを先に書く", MemoryType::Workflow, now - Duration::days(30));
+        let b = event("テストを先に書く", MemoryType::Workflow, now - Duration::days(30));
+
+        let short_half_life = rank_memories_at(&[a], "テストを先に書く", 5, 7.0, now);
+        let long_half_life = rank_memories_at(&[b], "テストを先に書く", 5, 60.0, now);
+        assert!(long_half_life[0].score > short_half_life[0].score);
+    }
+}
@@ -0,0 +1,301 @@
+//! Memory schema - unified format for multi-agent memory system.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Memory visibility scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryScope {
+    /// Current session only
+    Session,
+    /// User-wide, persistent
+    User,
+    /// Specific agent only
+    Agent,
+    /// Shared across all agents
+    Public,
+}
+
+/// Type of memory event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryType {
+    /// Factual observation
+    Observation,
+    /// Design/implementation decision
+    Decision,
+    /// Future plan or intent
+    Plan,
+    /// Code, file, or output reference
+    Artifact,
+    /// User preference
+    Preference,
+    /// Workflow pattern
+    Workflow,
+    /// Error pattern and solution
+    Error,
+    /// Research finding
+    Research,
+}
+
+/// Agent identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentType {
+    Claude,
+    Codex,
+    Gemini,
+    Copilot,
+    System,
+}
+
+/// Unified memory event schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryEvent {
+    /// Unique identifier
+    pub id: String,
+
+    /// Memory content
+    pub content: String,
+
+    /// Type of memory
+    pub memory_type: MemoryType,
+
+    /// Visibility scope
+    pub scope: MemoryScope,
+
+    /// Source agent
+    pub source_agent: AgentType,
+
+    /// Additional context
+    #[serde(default)]
+    pub context: String,
+
+    /// Confidence score (0.0 to 1.0)
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+
+    /// Time to live in days (None = permanent)
+    #[serde(default)]
+    pub ttl_days: Option<u32>,
+
+    /// Tags for categorization
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Additional metadata
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+
+    /// Creation timestamp (ISO 8601)
+    pub created_at: String,
+}
+
+fn default_confidence() -> f64 {
+    1.0
+}
+
+impl MemoryEvent {
+    /// Create a new memory event.
+    pub fn new(
+        content: impl Into<String>,
+        memory_type: MemoryType,
+        scope: MemoryScope,
+        source_agent: AgentType,
+    ) -> Self {
+        let now = chrono_now();
+        Self {
+            id: generate_id(),
+            content: content.into(),
+            memory_type,
+            scope,
+            source_agent,
+            context: String::new(),
+            confidence: 1.0,
+            ttl_days: None,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+            created_at: now,
+        }
+    }
+
+    /// Set context.
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = context.into();
+        self
+    }
+
+    /// Set confidence.
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set TTL.
+    pub fn with_ttl(mut self, days: u32) -> Self {
+        self.ttl_days = Some(days);
+        self
+    }
+
+    /// Add tag.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Whether this event's `ttl_days` has elapsed as of `now`. Events
+    /// without a TTL, or with an unparsable `created_at`, never expire.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        let Some(ttl_days) = self.ttl_days else {
+            return false;
+        };
+        let Some(age_days) = self.age_days(now) else {
+            return false;
+        };
+        age_days > ttl_days as f64
+    }
+
+    /// Parse `created_at` as RFC 3339. `None` if it's missing/malformed
+    /// (e.g. an event written before timestamps carried a timezone).
+    pub fn created_at_parsed(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Age of this event in days as of `now`. `None` if `created_at` can't
+    /// be parsed.
+    pub fn age_days(&self, now: DateTime<Utc>) -> Option<f64> {
+        let created = self.created_at_parsed()?;
+        Some((now - created).num_seconds() as f64 / 86400.0)
+    }
+}
+
+/// Generate a unique ID based on timestamp.
+fn generate_id() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let duration = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    format!("{}{:06}", duration.as_secs(), duration.subsec_micros())
+}
+
+/// Get the current time as an RFC 3339 / ISO 8601 UTC timestamp
+/// (`YYYY-MM-DDThh:mm:ssZ`), parseable by [`MemoryEvent::created_at_parsed`].
+fn chrono_now() -> String {
+    Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_event_creation() {
+        let event = MemoryEvent::new(
+            "Test memory",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::Claude,
+        );
+        assert_eq!(event.content, "Test memory");
+        assert_eq!(event.memory_type, MemoryType::Observation);
+        assert_eq!(event.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_memory_event_builder() {
+        let event = MemoryEvent::new(
+            "Preference",
+            MemoryType::Preference,
+            MemoryScope::User,
+            AgentType::System,
+        )
+        .with_context("User said")
+        .with_confidence(0.9)
+        .with_tag("pr")
+        .with_tag("japanese");
+
+        assert_eq!(event.context, "User said");
+        assert_eq!(event.confidence, 0.9);
+        assert_eq!(event.tags, vec!["pr", "japanese"]);
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now();
+
+        let mut permanent = MemoryEvent::new(
+            "No TTL",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        );
+        permanent.created_at = (now - chrono::Duration::days(365)).to_rfc3339();
+        assert!(!permanent.is_expired(now));
+
+        let mut expired = MemoryEvent::new(
+            "Stale",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        )
+        .with_ttl(7);
+        expired.created_at = (now - chrono::Duration::days(30)).to_rfc3339();
+        assert!(expired.is_expired(now));
+
+        let mut fresh = MemoryEvent::new(
+            "Recent",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        )
+        .with_ttl(7);
+        fresh.created_at = (now - chrono::Duration::days(1)).to_rfc3339();
+        assert!(!fresh.is_expired(now));
+    }
+
+    #[test]
+    fn test_chrono_now_is_parseable_rfc3339() {
+        let event = MemoryEvent::new(
+            "Test",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        );
+        assert!(event.created_at.ends_with('Z'));
+        assert!(event.created_at_parsed().is_some());
+    }
+
+    #[test]
+    fn test_age_days() {
+        let now = Utc::now();
+        let mut event = MemoryEvent::new(
+            "Test",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        );
+        event.created_at = (now - chrono::Duration::days(10)).to_rfc3339();
+        let age = event.age_days(now).unwrap();
+        assert!((age - 10.0).abs() < 0.01);
+
+        event.created_at = "not-a-timestamp".to_string();
+        assert!(event.age_days(now).is_none());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let event = MemoryEvent::new(
+            "Test",
+            MemoryType::Decision,
+            MemoryScope::Session,
+            AgentType::Codex,
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"memory_type\":\"decision\""));
+        assert!(json.contains("\"scope\":\"session\""));
+    }
+}
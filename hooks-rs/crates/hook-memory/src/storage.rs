@@ -1,11 +1,17 @@
 //! JSONL storage for memory events.
 
+use crate::rank::{rank_memories, RankedMemory};
 use crate::schema::{MemoryEvent, MemoryScope, MemoryType};
 use anyhow::{Context, Result, anyhow};
 use camino::Utf8PathBuf;
+use chrono::Utc;
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 
+/// Once the JSONL file grows past this many lines, `append` opportunistically
+/// compacts it to drop expired events rather than letting it grow unbounded.
+const COMPACT_LINE_THRESHOLD: usize = 500;
+
 /// JSONL-based memory storage.
 #[derive(Debug, Clone)]
 pub struct MemoryStorage {
@@ -63,7 +69,8 @@ impl MemoryStorage {
         Ok(())
     }
 
-    /// Append a memory event to storage.
+    /// Append a memory event to storage, then opportunistically compact if
+    /// the file has grown past `COMPACT_LINE_THRESHOLD` lines.
     pub fn append(&self, event: &MemoryEvent) -> Result<()> {
         self.ensure_dir()?;
 
@@ -75,10 +82,14 @@ impl MemoryStorage {
 
         let line = serde_json::to_string(event)?;
         writeln!(file, "{}", line)?;
+        drop(file);
+
+        self.maybe_compact()?;
         Ok(())
     }
 
-    /// Load all memory events from storage.
+    /// Load all memory events from storage, silently dropping any whose
+    /// `ttl_days` has elapsed so expired memories never surface.
     pub fn load_all(&self) -> Result<Vec<MemoryEvent>> {
         if !self.path.exists() {
             return Ok(Vec::new());
@@ -89,6 +100,7 @@ impl MemoryStorage {
 
         let reader = BufReader::new(file);
         let mut events = Vec::new();
+        let now = Utc::now();
 
         for line in reader.lines() {
             let line = line?;
@@ -96,6 +108,7 @@ impl MemoryStorage {
                 continue;
             }
             match serde_json::from_str::<MemoryEvent>(&line) {
+                Ok(event) if event.is_expired(now) => {}
                 Ok(event) => events.push(event),
                 Err(e) => {
                     // Log error but continue
@@ -107,7 +120,45 @@ impl MemoryStorage {
         Ok(events)
     }
 
-    /// Load memories filtered by type.
+    /// Rewrite the JSONL file to contain only live (non-expired) events,
+    /// via a temp file + rename so readers never see a partial file.
+    pub fn compact(&self) -> Result<()> {
+        let live = self.load_all()?;
+        self.ensure_dir()?;
+
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        {
+            let mut tmp = fs::File::create(&tmp_path)
+                .with_context(|| format!("Failed to create temp file: {}", tmp_path))?;
+            for event in &live {
+                writeln!(tmp, "{}", serde_json::to_string(event)?)?;
+            }
+        }
+
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to rename {} to {}", tmp_path, self.path))?;
+        Ok(())
+    }
+
+    /// Compact the file if its line count has passed `COMPACT_LINE_THRESHOLD`.
+    fn maybe_compact(&self) -> Result<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let file = fs::File::open(&self.path)
+            .with_context(|| format!("Failed to open storage: {}", self.path))?;
+        let line_count = BufReader::new(file).lines().count();
+
+        if line_count > COMPACT_LINE_THRESHOLD {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Load memories filtered by type. Expired events are already dropped
+    /// by `load_all`, so hooks like the session-start loader never see
+    /// stale data.
     pub fn load_by_type(&self, memory_type: MemoryType) -> Result<Vec<MemoryEvent>> {
         let all = self.load_all()?;
         Ok(all
@@ -149,6 +200,14 @@ impl MemoryStorage {
     pub fn count(&self) -> Result<usize> {
         Ok(self.load_all()?.len())
     }
+
+    /// Relevance-rank stored memories against `prompt` (BM25 similarity +
+    /// recency + confidence + tag-match bonus, see `crate::rank`) and
+    /// return the top `limit` across all types and scopes.
+    pub fn query(&self, prompt: &str, limit: usize) -> Result<Vec<RankedMemory>> {
+        let all = self.load_all()?;
+        Ok(rank_memories(&all, prompt, limit))
+    }
 }
 
 
@@ -179,6 +238,69 @@ mod tests {
         assert_eq!(loaded[0].content, "Test memory");
     }
 
+    #[test]
+    fn test_load_all_drops_expired_events() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.jsonl");
+        let storage = MemoryStorage::new(Utf8PathBuf::from_path_buf(path).unwrap());
+
+        let mut expired = MemoryEvent::new(
+            "Stale",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        )
+        .with_ttl(1);
+        expired.created_at = (Utc::now() - chrono::Duration::days(30)).to_rfc3339();
+        storage.append(&expired).unwrap();
+
+        storage
+            .append(&MemoryEvent::new(
+                "Fresh",
+                MemoryType::Observation,
+                MemoryScope::User,
+                AgentType::System,
+            ))
+            .unwrap();
+
+        let loaded = storage.load_all().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].content, "Fresh");
+    }
+
+    #[test]
+    fn test_compact_rewrites_only_live_events() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.jsonl");
+        let storage = MemoryStorage::new(Utf8PathBuf::from_path_buf(path.clone()).unwrap());
+
+        let mut expired = MemoryEvent::new(
+            "Stale",
+            MemoryType::Observation,
+            MemoryScope::User,
+            AgentType::System,
+        )
+        .with_ttl(1);
+        expired.created_at = (Utc::now() - chrono::Duration::days(30)).to_rfc3339();
+        storage.append(&expired).unwrap();
+
+        storage
+            .append(&MemoryEvent::new(
+                "Fresh",
+                MemoryType::Observation,
+                MemoryScope::User,
+                AgentType::System,
+            ))
+            .unwrap();
+
+        storage.compact().unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert_eq!(raw.lines().count(), 1);
+        assert!(raw.contains("Fresh"));
+        assert!(!raw.contains("Stale"));
+    }
+
     #[test]
     fn test_search() {
         let dir = tempdir().unwrap();
@@ -208,6 +330,35 @@ mod tests {
         assert!(results[0].content.contains("日本語"));
     }
 
+    #[test]
+    fn test_query_ranks_across_types() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.jsonl");
+        let storage = MemoryStorage::new(Utf8PathBuf::from_path_buf(path).unwrap());
+
+        storage
+            .append(&MemoryEvent::new(
+                "PRは日本語で書く",
+                MemoryType::Preference,
+                MemoryScope::User,
+                AgentType::System,
+            ))
+            .unwrap();
+
+        storage
+            .append(&MemoryEvent::new(
+                "今日は晴れです",
+                MemoryType::Observation,
+                MemoryScope::User,
+                AgentType::System,
+            ))
+            .unwrap();
+
+        let ranked = storage.query("日本語で書いて", 5).unwrap();
+        assert!(!ranked.is_empty());
+        assert!(ranked[0].event.content.contains("日本語"));
+    }
+
     #[test]
     fn test_load_by_type() {
         let dir = tempdir().unwrap();
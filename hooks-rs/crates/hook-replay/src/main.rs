@@ -0,0 +1,502 @@
+//! Replay-based regression harness for hook decision logic.
+//!
+//! Reads recorded `HookInput` JSON fixtures from a directory tree
+//! (`<fixtures_dir>/<hook>/<case>.input.json`), pipes each through the
+//! named hook's `run(&HookInput) -> Option<HookOutput>`, and asserts the
+//! result against a stored `<case>.golden.json` (a missing golden, or one
+//! containing `null`, means the hook is expected to stay silent).
+//!
+//! Hooks expose `run` from a library target instead of only `main` (see
+//! `enforce_no_merge`, `pre_tool_recall`, `post_tool_record`,
+//! `post_implementation_review`) specifically so this harness can call
+//! them in-process rather than spawning a subprocess per fixture.
+//!
+//! A second mode, `--log`, replays real recorded sessions instead of hand
+//! -written fixtures: it reads a `hook-debug.jsonl` blackbox audit log
+//! (see `hook_common::audit` and `HookOutput::write_stdout_logged_for_replay`),
+//! reconstructs the `HookInput` for each entry that opted into replay
+//! logging, re-runs it through the same `hook_registry`, and reports any
+//! entry whose current decision diverges from what was logged at the
+//! time -- e.g. after editing a keyword list or ACL config. This turns a
+//! recorded session into a regression fixture without having to
+//! hand-author one.
+//!
+//! Usage: `hook-replay <fixtures-dir> [--hook NAME] [--tool TOOL] [--update]`
+//!   --hook NAME    only replay fixtures under `<fixtures-dir>/NAME`
+//!   --tool TOOL    only replay fixtures whose `tool_name` matches TOOL
+//!   --update       rewrite goldens to match current output instead of
+//!                  asserting against them
+//!
+//! Usage: `hook-replay --log <hook-debug.jsonl> [--hook NAME]`
+//!   --hook NAME    only replay entries logged by hook NAME
+
+use anyhow::{Context, Result};
+use hook_common::input::{HookInput, ToolInput};
+use hook_common::output::{HookOutput, PermissionDecision};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+type HookFn = fn(&HookInput) -> Option<HookOutput>;
+
+/// Hooks whose core logic is replayable, keyed by their fixture directory
+/// name.
+fn hook_registry() -> Vec<(&'static str, HookFn)> {
+    vec![
+        ("enforce-no-merge", enforce_no_merge::run as HookFn),
+        ("pre-tool-recall", pre_tool_recall::run as HookFn),
+        ("post-tool-record", post_tool_record::run as HookFn),
+        ("post-implementation-review", post_implementation_review::run as HookFn),
+    ]
+}
+
+struct Args {
+    fixtures_dir: PathBuf,
+    hook_filter: Option<String>,
+    tool_filter: Option<String>,
+    update: bool,
+}
+
+fn parse_args(argv: impl Iterator<Item = String>) -> Result<Args> {
+    let mut fixtures_dir = None;
+    let mut hook_filter = None;
+    let mut tool_filter = None;
+    let mut update = false;
+
+    let mut args = argv;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--update" => update = true,
+            "--hook" => hook_filter = args.next(),
+            "--tool" => tool_filter = args.next(),
+            other => fixtures_dir = Some(PathBuf::from(other)),
+        }
+    }
+
+    Ok(Args {
+        fixtures_dir: fixtures_dir.context(
+            "usage: hook-replay <fixtures-dir> [--hook NAME] [--tool TOOL] [--update]",
+        )?,
+        hook_filter,
+        tool_filter,
+        update,
+    })
+}
+
+struct LogArgs {
+    log_path: PathBuf,
+    hook_filter: Option<String>,
+}
+
+fn parse_log_args(argv: impl Iterator<Item = String>) -> Result<LogArgs> {
+    let mut log_path = None;
+    let mut hook_filter = None;
+
+    let mut args = argv;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hook" => hook_filter = args.next(),
+            other => log_path = Some(PathBuf::from(other)),
+        }
+    }
+
+    Ok(LogArgs {
+        log_path: log_path.context("usage: hook-replay --log <hook-debug.jsonl> [--hook NAME]")?,
+        hook_filter,
+    })
+}
+
+struct CaseResult {
+    hook: String,
+    case: String,
+    passed: bool,
+}
+
+fn main() -> Result<()> {
+    let mut argv: Vec<String> = std::env::args().skip(1).collect();
+
+    let results = if let Some(pos) = argv.iter().position(|a| a == "--log") {
+        argv.remove(pos);
+        let args = parse_log_args(argv.into_iter())?;
+        replay_log(&args)?
+    } else {
+        let args = parse_args(argv.into_iter())?;
+        replay(&args)?
+    };
+
+    print_summary(&results);
+
+    if results.iter().any(|r| !r.passed) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// One line of a `hook-debug.jsonl` blackbox audit log, as written by
+/// `HookOutput::write_stdout_logged_for_replay`. Entries logged via the
+/// plain `write_stdout_logged` have no `hook_name`/`tool_input` and are
+/// skipped -- they were never meant to be replayed.
+#[derive(Debug, Deserialize)]
+struct LoggedEntry {
+    tool_name: String,
+    permission_decision: Option<PermissionDecision>,
+    blocking_error: Option<String>,
+    reason: Option<String>,
+    #[serde(default)]
+    hook_name: Option<String>,
+    #[serde(default)]
+    tool_input: Option<serde_json::Value>,
+}
+
+fn replay_log(args: &LogArgs) -> Result<Vec<CaseResult>> {
+    let registry: BTreeMap<&str, HookFn> = hook_registry().into_iter().collect();
+    let contents = std::fs::read_to_string(&args.log_path)
+        .with_context(|| format!("reading log {}", args.log_path.display()))?;
+
+    let mut results = Vec::new();
+    for (line_no, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: LoggedEntry = serde_json::from_str(line)
+            .with_context(|| format!("parsing {}:{}", args.log_path.display(), line_no + 1))?;
+
+        let Some(hook_name) = &entry.hook_name else { continue };
+        if let Some(filter) = &args.hook_filter {
+            if filter != hook_name {
+                continue;
+            }
+        }
+        let Some(run_fn) = registry.get(hook_name.as_str()) else { continue };
+        let Some(tool_input_value) = &entry.tool_input else { continue };
+
+        let tool_input: ToolInput = serde_json::from_value(tool_input_value.clone())?;
+        let input = HookInput { tool_name: entry.tool_name.clone(), tool_input, ..Default::default() };
+
+        let actual = run_fn(&input);
+        let passed = decision_matches(&entry, &actual);
+
+        results.push(CaseResult { hook: hook_name.clone(), case: format!("line {}", line_no + 1), passed });
+    }
+
+    Ok(results)
+}
+
+/// Whether `actual`'s decision matches what was logged: same permission
+/// decision, and the same blocking-error/context text (whichever the
+/// logged entry used as its `reason`).
+fn decision_matches(logged: &LoggedEntry, actual: &Option<HookOutput>) -> bool {
+    let actual_decision = actual.as_ref().and_then(|o| o.hook_specific_output.permission_decision);
+    if actual_decision != logged.permission_decision {
+        return false;
+    }
+
+    let actual_reason = actual.as_ref().and_then(|o| {
+        o.hook_specific_output
+            .blocking_error
+            .clone()
+            .or_else(|| o.hook_specific_output.additional_context.clone())
+    });
+    let logged_reason = logged.blocking_error.clone().or_else(|| logged.reason.clone());
+    actual_reason == logged_reason
+}
+
+fn replay(args: &Args) -> Result<Vec<CaseResult>> {
+    let mut results = Vec::new();
+
+    for (hook_name, run_fn) in hook_registry() {
+        if let Some(filter) = &args.hook_filter {
+            if filter != hook_name {
+                continue;
+            }
+        }
+
+        let hook_dir = args.fixtures_dir.join(hook_name);
+        if !hook_dir.is_dir() {
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&hook_dir)
+            .with_context(|| format!("reading fixture dir {}", hook_dir.display()))?
+        {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(case) = file_name.strip_suffix(".input.json") else {
+                continue;
+            };
+
+            let input: HookInput = serde_json::from_str(&std::fs::read_to_string(&path)?)
+                .with_context(|| format!("parsing fixture {}", path.display()))?;
+
+            if let Some(tool_filter) = &args.tool_filter {
+                if &input.tool_name != tool_filter {
+                    continue;
+                }
+            }
+
+            let output = run_fn(&input);
+            let golden_path = hook_dir.join(format!("{}.golden.json", case));
+
+            if args.update {
+                write_golden(&golden_path, &output)?;
+                results.push(CaseResult {
+                    hook: hook_name.to_string(),
+                    case: case.to_string(),
+                    passed: true,
+                });
+                continue;
+            }
+
+            let expected = read_golden(&golden_path)?;
+            let actual = output.as_ref().map(serde_json::to_value).transpose()?;
+            results.push(CaseResult {
+                hook: hook_name.to_string(),
+                case: case.to_string(),
+                passed: actual == expected,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+fn read_golden(path: &Path) -> Result<Option<serde_json::Value>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&contents)?;
+    Ok(if value.is_null() { None } else { Some(value) })
+}
+
+fn write_golden(path: &Path, output: &Option<HookOutput>) -> Result<()> {
+    let value = match output {
+        Some(output) => serde_json::to_value(output)?,
+        None => serde_json::Value::Null,
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&value)?)?;
+    Ok(())
+}
+
+/// Per-hook pass/fail tally, like a test runner's per-file results.
+fn print_summary(results: &[CaseResult]) {
+    let mut by_hook: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+    for r in results {
+        if !r.passed {
+            println!("FAIL {}/{}", r.hook, r.case);
+        }
+        let entry = by_hook.entry(&r.hook).or_insert((0, 0));
+        entry.0 += 1;
+        if r.passed {
+            entry.1 += 1;
+        }
+    }
+
+    println!();
+    for (hook, (total, passed)) in &by_hook {
+        println!("{}: {}/{} passed", hook, passed, total);
+    }
+
+    let total = results.len();
+    let passed = results.iter().filter(|r| r.passed).count();
+    println!("\n{}/{} fixtures passed", passed, total);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_fixture(dir: &Path, hook: &str, case: &str, input_json: &str, golden_json: Option<&str>) {
+        let hook_dir = dir.join(hook);
+        std::fs::create_dir_all(&hook_dir).unwrap();
+        std::fs::write(hook_dir.join(format!("{}.input.json", case)), input_json).unwrap();
+        if let Some(golden) = golden_json {
+            std::fs::write(hook_dir.join(format!("{}.golden.json", case)), golden).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_args_requires_fixtures_dir() {
+        assert!(parse_args(std::iter::empty()).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_reads_flags() {
+        let args = parse_args(
+            ["fixtures", "--hook", "enforce-no-merge", "--tool", "Bash", "--update"]
+                .into_iter()
+                .map(String::from),
+        )
+        .unwrap();
+        assert_eq!(args.fixtures_dir, PathBuf::from("fixtures"));
+        assert_eq!(args.hook_filter.as_deref(), Some("enforce-no-merge"));
+        assert_eq!(args.tool_filter.as_deref(), Some("Bash"));
+        assert!(args.update);
+    }
+
+    #[test]
+    fn test_replay_matches_silent_golden() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            "enforce-no-merge",
+            "allowed",
+            r#"{"tool_name": "Bash", "tool_input": {"command": "git status"}}"#,
+            Some("null"),
+        );
+
+        let args = Args {
+            fixtures_dir: dir.path().to_path_buf(),
+            hook_filter: None,
+            tool_filter: None,
+            update: false,
+        };
+        let results = replay(&args).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed);
+    }
+
+    #[test]
+    fn test_replay_detects_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            "enforce-no-merge",
+            "blocked",
+            r#"{"tool_name": "Bash", "tool_input": {"command": "git merge main"}}"#,
+            Some("null"),
+        );
+
+        let args = Args {
+            fixtures_dir: dir.path().to_path_buf(),
+            hook_filter: None,
+            tool_filter: None,
+            update: false,
+        };
+        let results = replay(&args).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].passed);
+    }
+
+    #[test]
+    fn test_replay_update_writes_golden() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            "enforce-no-merge",
+            "blocked",
+            r#"{"tool_name": "Bash", "tool_input": {"command": "git merge main"}}"#,
+            None,
+        );
+
+        let args = Args {
+            fixtures_dir: dir.path().to_path_buf(),
+            hook_filter: None,
+            tool_filter: None,
+            update: true,
+        };
+        replay(&args).unwrap();
+
+        let golden_path = dir.path().join("enforce-no-merge").join("blocked.golden.json");
+        let golden: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(golden_path).unwrap()).unwrap();
+        assert_eq!(
+            golden["hookSpecificOutput"]["permissionDecision"],
+            serde_json::json!("deny")
+        );
+    }
+
+    #[test]
+    fn test_parse_log_args_requires_path() {
+        assert!(parse_log_args(std::iter::empty()).is_err());
+    }
+
+    #[test]
+    fn test_replay_log_matches_when_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("hook-debug.jsonl");
+        let logged = serde_json::json!({
+            "timestamp": "2026-01-01T00:00:00Z",
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Bash",
+            "permission_decision": "deny",
+            "blocking_error": null,
+            "reason": enforce_no_merge::BLOCK_MESSAGE,
+            "hook_name": "enforce-no-merge",
+            "tool_input": {"command": "gh pr merge 1"},
+        });
+        std::fs::write(&log_path, format!("{}\n", logged)).unwrap();
+
+        let args = LogArgs { log_path, hook_filter: None };
+        let results = replay_log(&args).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].passed);
+    }
+
+    #[test]
+    fn test_replay_log_detects_divergence() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("hook-debug.jsonl");
+        // Logged as allowed, but re-running `enforce-no-merge::run` on this
+        // command now denies it -- simulates a keyword-list change.
+        std::fs::write(
+            &log_path,
+            r#"{"timestamp":"2026-01-01T00:00:00Z","hook_event_name":"PreToolUse","tool_name":"Bash","permission_decision":null,"blocking_error":null,"reason":null,"hook_name":"enforce-no-merge","tool_input":{"command":"gh pr merge 1"}}
+"#,
+        )
+        .unwrap();
+
+        let args = LogArgs { log_path, hook_filter: None };
+        let results = replay_log(&args).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].passed);
+    }
+
+    #[test]
+    fn test_replay_log_skips_entries_without_hook_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("hook-debug.jsonl");
+        std::fs::write(
+            &log_path,
+            r#"{"timestamp":"2026-01-01T00:00:00Z","hook_event_name":"PreToolUse","tool_name":"Bash","permission_decision":"deny","blocking_error":null,"reason":"blocked"}
+"#,
+        )
+        .unwrap();
+
+        let args = LogArgs { log_path, hook_filter: None };
+        let results = replay_log(&args).unwrap();
+        assert_eq!(results.len(), 0);
+    }
+
+    #[test]
+    fn test_replay_filters_by_hook_and_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        write_fixture(
+            dir.path(),
+            "enforce-no-merge",
+            "allowed",
+            r#"{"tool_name": "Bash", "tool_input": {"command": "git status"}}"#,
+            Some("null"),
+        );
+        write_fixture(
+            dir.path(),
+            "post-tool-record",
+            "edit",
+            r#"{"tool_name": "Edit", "tool_input": {"file_path": "a.rs"}}"#,
+            Some("null"),
+        );
+
+        let args = Args {
+            fixtures_dir: dir.path().to_path_buf(),
+            hook_filter: Some("enforce-no-merge".to_string()),
+            tool_filter: None,
+            update: false,
+        };
+        let results = replay(&args).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].hook, "enforce-no-merge");
+    }
+}
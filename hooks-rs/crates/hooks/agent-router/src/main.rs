@@ -1,12 +1,38 @@
 //! UserPromptSubmit hook: Route to appropriate agent based on user intent.
 //!
-//! Priority:
-//! 1. Codex - Design, debugging, deep reasoning
-//! 2. Gemini - Research, multimodal, large context
-//! 3. Copilot - Everything else (cost-effective default)
+//! `detect_agent` used to return on the first matching keyword, so a prompt
+//! like "research why this architecture bug fails" picked whichever list
+//! happened to be scanned first rather than the strongest intent. It now
+//! scores each agent: for every trigger that matches, add that trigger's
+//! weight (default weight = its character length, so longer, more specific
+//! phrases outweigh short generic ones) to that agent's score and count the
+//! match, then pick the highest-scoring agent, breaking ties by the fixed
+//! priority Codex > Gemini > Copilot. Direct-task patterns still hard-override
+//! (any match forces `Direct` regardless of score) since there's never a
+//! reason to route a "git commit" request to an LLM.
+//!
+//! Trigger terms, their agent, and optional weight overrides are loadable
+//! from `.claude/config/router-triggers.toml`:
+//!
+//! ```toml
+//! [[triggers]]
+//! term = "segfault"
+//! agent = "codex"
+//! weight = 20
+//! ```
+//!
+//! so a project can extend the vocabulary without touching the source
+//! arrays below, which remain the built-in defaults.
+//!
+//! Before any scoring happens, the shared `hook_common::rules::RuleChain`
+//! extension pipeline gets a chance to route the prompt itself, so a team
+//! can override routing entirely via a TOML manifest under
+//! `.claude/hooks/plugins/` rather than editing this file.
 
 use anyhow::Result;
 use hook_common::prelude::*;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
 // Triggers for Codex (design, debugging, deep reasoning)
 const CODEX_TRIGGERS_JA: &[&str] = &[
@@ -59,7 +85,12 @@ const DIRECT_TASKS: &[&str] = &[
     "create file", "edit file",
 ];
 
-#[derive(Debug, PartialEq)]
+// Baseline score Copilot gets for any non-trivial prompt, so it still wins
+// when nothing else scores -- it's not a "trigger", just a floor.
+const COPILOT_BASELINE_SCORE: i64 = 1;
+const COPILOT_MIN_PROMPT_LEN: usize = 20;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 enum Agent {
     Direct,
     Codex,
@@ -67,6 +98,90 @@ enum Agent {
     Copilot,
 }
 
+impl Agent {
+    fn name(self) -> &'static str {
+        match self {
+            Agent::Direct => "Direct",
+            Agent::Codex => "Codex",
+            Agent::Gemini => "Gemini",
+            Agent::Copilot => "Copilot",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TriggerDef {
+    term: String,
+    agent: String,
+    #[serde(default)]
+    weight: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TriggersFile {
+    #[serde(default)]
+    triggers: Vec<TriggerDef>,
+}
+
+#[derive(Debug, Clone)]
+struct Trigger {
+    term: String,
+    weight: i64,
+}
+
+/// A scored trigger vocabulary for direct/codex/gemini routing, built from
+/// the compiled-in defaults plus any `.claude/config/router-triggers.toml`
+/// additions.
+struct RouterTriggers {
+    direct: Vec<Trigger>,
+    codex: Vec<Trigger>,
+    gemini: Vec<Trigger>,
+}
+
+impl RouterTriggers {
+    fn default_path(project_dir: &Path) -> PathBuf {
+        project_dir.join(".claude").join("config").join("router-triggers.toml")
+    }
+
+    fn load(path: &Path) -> Self {
+        let mut triggers = Self {
+            direct: default_triggers(DIRECT_TASKS),
+            codex: default_triggers(CODEX_TRIGGERS_JA).into_iter().chain(default_triggers(CODEX_TRIGGERS_EN)).collect(),
+            gemini: default_triggers(GEMINI_TRIGGERS_JA).into_iter().chain(default_triggers(GEMINI_TRIGGERS_EN)).collect(),
+        };
+
+        let file = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str::<TriggersFile>(&contents).ok())
+            .unwrap_or_default();
+
+        for def in file.triggers {
+            let weight = def.weight.unwrap_or(def.term.chars().count() as i64);
+            let trigger = Trigger { term: def.term, weight };
+            match def.agent.as_str() {
+                "direct" => triggers.direct.push(trigger),
+                "codex" => triggers.codex.push(trigger),
+                "gemini" => triggers.gemini.push(trigger),
+                _ => {}
+            }
+        }
+
+        triggers
+    }
+}
+
+fn default_triggers(terms: &[&str]) -> Vec<Trigger> {
+    terms.iter().map(|term| Trigger { term: term.to_string(), weight: term.chars().count() as i64 }).collect()
+}
+
+/// Sum of matched triggers' weights, and how many distinct triggers matched.
+fn score(triggers: &[Trigger], haystack_lower: &str) -> (i64, usize) {
+    triggers.iter().filter(|t| haystack_lower.contains(t.term.to_lowercase().as_str())).fold(
+        (0i64, 0usize),
+        |(score, count), t| (score + t.weight, count + 1),
+    )
+}
+
 fn main() -> Result<()> {
     let input = HookInput::from_stdin()?;
 
@@ -77,29 +192,39 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let (agent, trigger) = detect_agent(prompt);
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+
+    // Project-specific routing rules (see `hook_common::rules`) get first
+    // say over the built-in scoring below.
+    let rule_dir = RuleChain::default_dir(Path::new(&project_dir));
+    if let Some(output) = RuleChain::discover(&rule_dir).evaluate(&input, "UserPromptSubmit") {
+        output.write_stdout()?;
+        return Ok(());
+    }
+
+    let triggers = RouterTriggers::load(&RouterTriggers::default_path(Path::new(&project_dir)));
+
+    let (agent, detail) = detect_agent(prompt, &triggers);
 
     let context = match agent {
         Agent::Direct => return Ok(()),
         Agent::Codex => format!(
-            "[Agent: Codex] Detected '{}' - important task requiring deep reasoning. \
-             Use Codex for design decisions, debugging, or complex analysis. \
+            "[Agent: Codex] {}. Use Codex for design decisions, debugging, or complex analysis. \
              Command: `codex exec --model gpt-5.2-codex --sandbox read-only --full-auto \"...\"` \
              (via subagent for large outputs)",
-            trigger
+            detail
         ),
         Agent::Gemini => format!(
-            "[Agent: Gemini] Detected '{}' - specialized research/multimodal task. \
-             Use Gemini for research, large context analysis, or multimodal content. \
+            "[Agent: Gemini] {}. Use Gemini for research, large context analysis, or multimodal content. \
              Command: `gemini -p \"...\" 2>/dev/null` \
              (via subagent for large outputs)",
-            trigger
+            detail
         ),
         Agent::Copilot => format!(
-            "[Agent: Copilot] General task - consider using Copilot CLI for cost-effective \
-             execution with subagent capabilities. \
-             Command: `copilot -p \"...\" --model claude-opus-4.5 --allow-all --silent 2>/dev/null` \
-             (direct call OK for quick tasks)"
+            "[Agent: Copilot] {}. Consider using Copilot CLI for cost-effective execution with subagent \
+             capabilities. Command: `copilot -p \"...\" --model claude-opus-4.5 --allow-all --silent 2>/dev/null` \
+             (direct call OK for quick tasks)",
+            detail
         ),
     };
 
@@ -109,63 +234,106 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn detect_agent(prompt: &str) -> (Agent, String) {
-    let prompt_lower = prompt.to_lowercase();
+/// Score every agent, hard-override to `Direct` on any match there, and
+/// otherwise pick the highest score (ties broken by priority: Codex >
+/// Gemini > Copilot). The returned string explains the score gap between
+/// the top two agents, e.g. "Codex 14 vs Gemini 6".
+fn detect_agent(prompt: &str, triggers: &RouterTriggers) -> (Agent, String) {
+    let haystack = prompt.to_lowercase();
 
-    // Check direct tasks first
-    for task in DIRECT_TASKS {
-        if prompt_lower.contains(&task.to_lowercase()) {
-            return (Agent::Direct, task.to_string());
-        }
+    let (direct_score, direct_matches) = score(&triggers.direct, &haystack);
+    if direct_matches > 0 {
+        return (Agent::Direct, format!("score {}", direct_score));
     }
 
-    // Priority 1: Codex triggers
-    for trigger in CODEX_TRIGGERS_JA.iter().chain(CODEX_TRIGGERS_EN.iter()) {
-        if prompt_lower.contains(&trigger.to_lowercase()) {
-            return (Agent::Codex, trigger.to_string());
-        }
-    }
+    let (codex_score, _) = score(&triggers.codex, &haystack);
+    let (gemini_score, _) = score(&triggers.gemini, &haystack);
+    let copilot_score = if prompt.len() > COPILOT_MIN_PROMPT_LEN { COPILOT_BASELINE_SCORE } else { 0 };
 
-    // Priority 2: Gemini triggers
-    for trigger in GEMINI_TRIGGERS_JA.iter().chain(GEMINI_TRIGGERS_EN.iter()) {
-        if prompt_lower.contains(&trigger.to_lowercase()) {
-            return (Agent::Gemini, trigger.to_string());
-        }
-    }
+    let ranked = [(Agent::Codex, codex_score), (Agent::Gemini, gemini_score), (Agent::Copilot, copilot_score)];
 
-    // Priority 3: Copilot for non-trivial prompts
-    if prompt.len() > 20 {
-        return (Agent::Copilot, "general task".to_string());
-    }
+    let winner = ranked.iter().copied().fold(None::<(Agent, i64)>, |best, (agent, score)| match best {
+        Some((_, best_score)) if best_score >= score => best,
+        _ => Some((agent, score)),
+    });
 
-    (Agent::Direct, String::new())
+    let Some((winner_agent, winner_score)) = winner.filter(|(_, score)| *score > 0) else {
+        return (Agent::Direct, String::new());
+    };
+
+    let mut sorted = ranked.to_vec();
+    sorted.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    let detail = format!(
+        "score {} ({} {} vs {} {})",
+        winner_score, sorted[0].0.name(), sorted[0].1, sorted[1].0.name(), sorted[1].1
+    );
+
+    (winner_agent, detail)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn default_triggers_set() -> RouterTriggers {
+        RouterTriggers::load(Path::new("/nonexistent/router-triggers.toml"))
+    }
+
     #[test]
-    fn test_detect_codex() {
-        let (agent, _) = detect_agent("このエラーをデバッグして");
+    fn test_detect_codex_on_strong_match() {
+        let triggers = default_triggers_set();
+        let (agent, _) = detect_agent("このエラーをデバッグして", &triggers);
         assert_eq!(agent, Agent::Codex);
 
-        let (agent, _) = detect_agent("How should I design this feature?");
+        let (agent, _) = detect_agent("How should I design this feature?", &triggers);
         assert_eq!(agent, Agent::Codex);
     }
 
     #[test]
-    fn test_detect_gemini() {
-        let (agent, _) = detect_agent("このライブラリについて調べて");
+    fn test_detect_gemini_on_strong_match() {
+        let triggers = default_triggers_set();
+        let (agent, _) = detect_agent("このライブラリについて調べて", &triggers);
         assert_eq!(agent, Agent::Gemini);
 
-        let (agent, _) = detect_agent("Research the latest documentation");
+        let (agent, _) = detect_agent("Research the latest documentation", &triggers);
         assert_eq!(agent, Agent::Gemini);
     }
 
     #[test]
-    fn test_detect_direct() {
-        let (agent, _) = detect_agent("git commit please");
+    fn test_detect_direct_hard_overrides() {
+        let triggers = default_triggers_set();
+        let (agent, _) = detect_agent("git commit please, also research the bug", &triggers);
         assert_eq!(agent, Agent::Direct);
     }
+
+    #[test]
+    fn test_weighted_scoring_picks_stronger_intent() {
+        let triggers = default_triggers_set();
+        // "research" (8) + "architecture" (12) + "bug" (3) on the gemini/codex
+        // side, but codex also matches "fails" -- codex should out-score
+        // gemini's single "research" match.
+        let (agent, detail) = detect_agent("research why this architecture bug fails", &triggers);
+        assert_eq!(agent, Agent::Codex);
+        assert!(detail.contains("vs"));
+    }
+
+    #[test]
+    fn test_custom_trigger_config_adds_weight() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("router-triggers.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[triggers]]
+            term = "flaky test"
+            agent = "codex"
+            weight = 100
+            "#,
+        )
+        .unwrap();
+
+        let triggers = RouterTriggers::load(&path);
+        let (agent, _) = detect_agent("the flaky test needs attention", &triggers);
+        assert_eq!(agent, Agent::Codex);
+    }
 }
@@ -1,11 +1,17 @@
 //! UserPromptSubmit hook: Auto-create feature branch and draft PR on session start.
 //!
-//! Ensures every session has an open PR before any work begins.
+//! Ensures every session has an open PR before any work begins. Branch
+//! creation, local-branch listing and the HEAD hash go through
+//! `hook_common::git` in-process (gix-backed); merged-branch cleanup and
+//! PR-branch syncing also call `hook_common::git`, but its fetch, checkout
+//! and branch deletion run the `git` CLI, and `git pull` is run as a shell
+//! command here. PR create/list goes through the `gh` CLI.
 
 use anyhow::Result;
+use hook_common::fs::{Fs, RealFs};
+use hook_common::git;
 use hook_common::prelude::*;
 use hook_common::subprocess::run_command_with_timeout;
-use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -13,6 +19,7 @@ const TIMEOUT: Duration = Duration::from_secs(30);
 
 fn main() -> Result<()> {
     let _input = HookInput::from_stdin()?;
+    let fs = RealFs;
 
     let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
     let marker_file = PathBuf::from(&project_dir)
@@ -21,20 +28,20 @@ fn main() -> Result<()> {
     let session_id = get_session_id();
 
     // Create conductor marker at session start
-    create_conductor_marker(&project_dir);
+    create_conductor_marker(&fs, &project_dir);
 
     // Skip if marker exists AND is for current session
-    if is_marker_valid(&marker_file, &session_id) {
+    if is_marker_valid(&fs, &marker_file, &session_id) {
         return Ok(());
     }
 
     // New session - delete old marker
-    if marker_file.exists() {
-        let _ = fs::remove_file(&marker_file);
+    if fs.exists(&marker_file) {
+        let _ = fs.remove_file(&marker_file);
     }
 
     // Cleanup merged branches
-    cleanup_merged_branches();
+    cleanup_merged_branches(&project_dir);
 
     // Check for existing open PR
     if let Some(pr) = get_first_open_pr() {
@@ -44,10 +51,10 @@ fn main() -> Result<()> {
 
         // Sync local branch with the PR branch
         if !pr_branch.is_empty() {
-            sync_branch_with_pr(&pr_branch);
+            sync_branch_with_pr(&project_dir, &pr_branch);
         }
 
-        write_marker(&marker_file, &session_id, &format!("existing:{}:#{}", pr_branch, pr_number));
+        write_marker(&fs, &marker_file, &session_id, &format!("existing:{}:#{}", pr_branch, pr_number));
 
         // Output additional context for Claude
         let context = format!("📋 既存のPR #{} を使用（ブランチ同期済み）: {}", pr_number, pr_url);
@@ -57,9 +64,9 @@ fn main() -> Result<()> {
     }
 
     // No open PR - create one
-    match create_branch_and_pr() {
+    match create_branch_and_pr(&project_dir) {
         Ok((branch_name, pr_url)) => {
-            write_marker(&marker_file, &session_id, &format!("created:{}", branch_name));
+            write_marker(&fs, &marker_file, &session_id, &format!("created:{}", branch_name));
             let context = format!("✅ Draft PR を自動作成: {}", pr_url);
             let output = HookOutput::user_prompt_submit().with_context(context);
             output.write_stdout()?;
@@ -105,9 +112,9 @@ fn get_first_open_pr() -> Option<PullRequest> {
     })
 }
 
-fn get_short_hash() -> String {
-    run_command_with_timeout("git rev-parse --short HEAD", TIMEOUT)
-        .map(|r| r.stdout.trim().to_string())
+fn get_short_hash(project_dir: &str) -> String {
+    git::open(project_dir)
+        .and_then(|repo| git::current_short_hash(&repo))
         .unwrap_or_else(|_| {
             use std::time::{SystemTime, UNIX_EPOCH};
             let ts = SystemTime::now()
@@ -118,8 +125,8 @@ fn get_short_hash() -> String {
         })
 }
 
-fn cleanup_merged_branches() {
-    let _ = run_command_with_timeout("git fetch origin", TIMEOUT);
+fn cleanup_merged_branches(project_dir: &str) {
+    let _ = git::fetch(project_dir, "origin", None);
 
     let result = run_command_with_timeout(
         "gh pr list --state merged --json headRefName --limit 20",
@@ -136,78 +143,79 @@ fn cleanup_merged_branches() {
         })
         .unwrap_or_default();
 
-    if let Ok(result) = run_command_with_timeout("git branch", TIMEOUT) {
-        for line in result.stdout.lines() {
-            let branch = line.trim().trim_start_matches("* ").trim();
-            if merged_branches.contains(&branch.to_string()) && branch != "main" {
-                let _ = run_command_with_timeout(&format!("git branch -D {}", branch), TIMEOUT);
+    if let Ok(repo) = git::open(project_dir) {
+        if let Ok(branches) = git::list_local_branches(&repo) {
+            for branch in branches {
+                if merged_branches.contains(&branch) && branch != "main" {
+                    let _ = git::delete_branch(project_dir, &branch);
+                }
             }
         }
     }
 
-    let _ = run_command_with_timeout("git checkout main", TIMEOUT);
-    let _ = run_command_with_timeout("git pull origin main", TIMEOUT);
+    let _ = git::checkout(project_dir, "main");
+    let _ = run_command_with_timeout(
+        &format!("cd {} && git pull origin main", project_dir),
+        TIMEOUT,
+    );
 }
 
-fn sync_branch_with_pr(branch_name: &str) -> bool {
-    let _ = run_command_with_timeout(&format!("git fetch origin {}", branch_name), TIMEOUT);
+fn sync_branch_with_pr(project_dir: &str, branch_name: &str) -> bool {
+    let _ = git::fetch(project_dir, "origin", Some(branch_name));
 
-    if run_command_with_timeout(&format!("git checkout {}", branch_name), TIMEOUT)
-        .map(|r| r.success)
-        .unwrap_or(false)
-    {
-        let _ = run_command_with_timeout(&format!("git pull origin {}", branch_name), TIMEOUT);
+    if git::checkout(project_dir, branch_name).is_ok() {
+        let _ = run_command_with_timeout(
+            &format!("cd {} && git pull origin {}", project_dir, branch_name),
+            TIMEOUT,
+        );
         return true;
     }
 
     // Create tracking branch
     let _ = run_command_with_timeout(
-        &format!("git checkout -b {} origin/{}", branch_name, branch_name),
+        &format!(
+            "cd {} && git checkout -b {} origin/{}",
+            project_dir, branch_name, branch_name
+        ),
         TIMEOUT,
     );
 
     true
 }
 
-fn create_branch_and_pr() -> Result<(String, String)> {
-    let short_hash = get_short_hash();
+fn create_branch_and_pr(project_dir: &str) -> Result<(String, String)> {
+    let short_hash = get_short_hash(project_dir);
     let branch_name = format!("feature/session-{}", short_hash);
 
     // Create new branch from main
-    let _ = run_command_with_timeout("git checkout main", TIMEOUT);
-    let result = run_command_with_timeout(&format!("git checkout -b {}", branch_name), TIMEOUT)?;
-    if !result.success {
-        anyhow::bail!("Failed to create branch: {}", branch_name);
-    }
-
-    // Create initial commit if there are uncommitted changes
-    let status = run_command_with_timeout("git status --porcelain", TIMEOUT)?;
-    if !status.stdout.trim().is_empty() {
-        let _ = run_command_with_timeout("git add -A", TIMEOUT);
+    let _ = git::checkout(project_dir, "main");
+    let repo = git::open(project_dir)?;
+    git::create_branch_from(&repo, &branch_name, "main")?;
+    git::checkout(project_dir, &branch_name)?;
+
+    // Create initial commit if there are uncommitted changes. Goes
+    // through `hook_common::vcs_cache` rather than spawning `git status`
+    // directly, since `ensure-pr-open` may have just asked the same
+    // question this session.
+    if has_uncommitted_changes(project_dir) {
         let commit_msg = format!(
             "WIP: Session {}\n\nCo-Authored-By: Claude Opus 4.5 <noreply@anthropic.com>",
             short_hash
         );
-        let _ = run_command_with_timeout(
-            &format!("git commit -m \"{}\"", commit_msg.replace('"', "\\\"")),
-            TIMEOUT,
-        );
+        let _ = git::commit_all(project_dir, &commit_msg);
     }
 
     // Push branch
-    let push_result = run_command_with_timeout(
-        &format!("git push -u origin {}", branch_name),
-        TIMEOUT,
-    )?;
-    if !push_result.success {
-        anyhow::bail!("Failed to push: {}", push_result.stderr);
-    }
+    git::push(project_dir, "origin", &branch_name)?;
 
-    // Create PR
+    // Create PR, with a body summarizing the actual diff against main.
     let pr_title = format!("WIP: Session {}", short_hash);
+    let diff_summary = hook_common::diff::render_unified(project_dir, "main", &branch_name)
+        .unwrap_or_else(|_| "🤖 Auto-created draft PR for session.".to_string());
+    let body_file = write_pr_body_file(project_dir, &diff_summary)?;
     let pr_cmd = format!(
-        "gh pr create --draft --head {} --base main --title \"{}\" --body \"🤖 Auto-created draft PR for session.\"",
-        branch_name, pr_title
+        "gh pr create --draft --head {} --base main --title \"{}\" --body-file {}",
+        branch_name, pr_title, body_file
     );
 
     let pr_result = run_command_with_timeout(&pr_cmd, TIMEOUT)?;
@@ -219,32 +227,44 @@ fn create_branch_and_pr() -> Result<(String, String)> {
     }
 }
 
-fn is_marker_valid(marker_file: &Path, session_id: &str) -> bool {
-    if !marker_file.exists() {
+/// Write the PR body to a temp file and return its path, so `gh pr create
+/// --body-file` can embed a multi-line diff without shell-quoting it.
+fn write_pr_body_file(project_dir: &str, body: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!(
+        "claude-pr-body-{}-{}.md",
+        std::process::id(),
+        project_dir.replace(['/', ' '], "_")
+    ));
+    std::fs::write(&path, body)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn is_marker_valid(fs: &dyn Fs, marker_file: &Path, session_id: &str) -> bool {
+    if !fs.exists(marker_file) {
         return false;
     }
 
-    fs::read_to_string(marker_file)
+    fs.read_to_string(marker_file)
         .map(|content| {
             content.split(':').next().map(|s| s == session_id).unwrap_or(false)
         })
         .unwrap_or(false)
 }
 
-fn write_marker(marker_file: &Path, session_id: &str, pr_info: &str) {
+fn write_marker(fs: &dyn Fs, marker_file: &Path, session_id: &str, pr_info: &str) {
     if let Some(parent) = marker_file.parent() {
-        let _ = fs::create_dir_all(parent);
+        let _ = fs.create_dir_all(parent);
     }
-    let _ = fs::write(marker_file, format!("{}:{}", session_id, pr_info));
+    let _ = fs.write(marker_file, &format!("{}:{}", session_id, pr_info));
 }
 
-fn create_conductor_marker(project_dir: &str) {
+fn create_conductor_marker(fs: &dyn Fs, project_dir: &str) {
     let marker_path = PathBuf::from(project_dir)
         .join(".claude")
         .join(".conductor-session");
 
     if let Some(parent) = marker_path.parent() {
-        let _ = fs::create_dir_all(parent);
+        let _ = fs.create_dir_all(parent);
     }
 
     let ppid = std::process::id();
@@ -258,12 +278,13 @@ fn create_conductor_marker(project_dir: &str) {
         "created_at": created_at
     });
 
-    let _ = fs::write(marker_path, serde_json::to_string(&marker_data).unwrap_or_default());
+    let _ = fs.write(&marker_path, &serde_json::to_string(&marker_data).unwrap_or_default());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hook_common::fs::FakeFs;
 
     #[test]
     fn test_get_session_id() {
@@ -273,7 +294,30 @@ mod tests {
 
     #[test]
     fn test_get_short_hash() {
-        let hash = get_short_hash();
+        let hash = get_short_hash(".");
         assert!(!hash.is_empty());
     }
+
+    #[test]
+    fn test_marker_round_trip_on_fake_fs() {
+        let fs = FakeFs::new();
+        let marker_file = PathBuf::from("/tmp/.session-pr-created");
+
+        assert!(!is_marker_valid(&fs, &marker_file, "session-1"));
+
+        write_marker(&fs, &marker_file, "session-1", "created:feature/x");
+        assert!(is_marker_valid(&fs, &marker_file, "session-1"));
+        assert!(!is_marker_valid(&fs, &marker_file, "session-2"));
+    }
+
+    #[test]
+    fn test_create_conductor_marker_writes_json() {
+        let fs = FakeFs::new();
+        create_conductor_marker(&fs, "/tmp/project");
+
+        let contents = fs
+            .read_to_string(Path::new("/tmp/project/.claude/.conductor-session"))
+            .unwrap();
+        assert!(contents.contains("ppid"));
+    }
 }
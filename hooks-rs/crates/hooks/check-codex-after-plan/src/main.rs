@@ -1,12 +1,15 @@
 //! PostToolUse hook: Suggest Codex review after Plan tasks.
 //!
 //! Runs after Task tool execution and suggests Codex consultation
-//! for reviewing plans and implementation strategies.
+//! for reviewing plans and implementation strategies. Indicator words are
+//! resolved through `hook_common::keywords` (group `"plan"`) rather than
+//! a hardcoded list, so a project can add domain terms or suppress noisy
+//! ones via `.claude/config/suggestions.toml` without a rebuild.
 
 use anyhow::Result;
 use hook_common::prelude::*;
 
-// Task descriptions that suggest planning/design work
+// Built-in defaults for the "plan" keyword group; see module doc.
 const PLAN_INDICATORS: &[&str] = &[
     "plan",
     "design",
@@ -31,7 +34,14 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    if let Some(reason) = should_suggest_codex_review(&input.tool_input) {
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+    let plan_group = KeywordGroup::load(
+        &KeywordGroup::default_path(std::path::Path::new(&project_dir)),
+        "plan",
+        PLAN_INDICATORS,
+    );
+
+    if let Some(reason) = should_suggest_codex_review(&input.tool_input, &plan_group) {
         let context = format!(
             "[Codex Review Suggestion] {}. \
              Consider having Codex review this plan for potential improvements. \
@@ -47,7 +57,10 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn should_suggest_codex_review(tool_input: &hook_common::input::ToolInput) -> Option<String> {
+fn should_suggest_codex_review(
+    tool_input: &hook_common::input::ToolInput,
+    plan_group: &KeywordGroup,
+) -> Option<String> {
     let subagent_type = tool_input
         .subagent_type
         .as_deref()
@@ -74,10 +87,8 @@ fn should_suggest_codex_review(tool_input: &hook_common::input::ToolInput) -> Op
 
     // Check description/prompt for planning keywords
     let combined_text = format!("{} {}", description, prompt);
-    for indicator in PLAN_INDICATORS {
-        if combined_text.contains(indicator) {
-            return Some(format!("Task involves '{}'", indicator));
-        }
+    if let Some(indicator) = plan_group.first_match(&combined_text) {
+        return Some(format!("Task involves '{}'", indicator));
     }
 
     None
@@ -85,10 +96,21 @@ fn should_suggest_codex_review(tool_input: &hook_common::input::ToolInput) -> Op
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_plan_indicators() {
-        let indicators = super::PLAN_INDICATORS;
-        assert!(indicators.contains(&"plan"));
-        assert!(indicators.contains(&"design"));
+        assert!(PLAN_INDICATORS.contains(&"plan"));
+        assert!(PLAN_INDICATORS.contains(&"design"));
+    }
+
+    #[test]
+    fn test_should_suggest_codex_review_matches_default_indicator() {
+        let group = KeywordGroup::load(std::path::Path::new("/nonexistent"), "plan", PLAN_INDICATORS);
+        let tool_input = hook_common::input::ToolInput {
+            prompt: Some("let's design this module".to_string()),
+            ..Default::default()
+        };
+        assert!(should_suggest_codex_review(&tool_input, &group).is_some());
     }
 }
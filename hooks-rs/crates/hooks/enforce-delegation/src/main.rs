@@ -1,16 +1,30 @@
 //! PreToolUse hook: Enforce delegation for Conductor.
 //!
-//! 2-tier hierarchy: Conductor delegates to Musician.
-//! Counts work tool usage without delegation and warns/blocks after thresholds.
+//! Counts work tool usage without delegation and warns/blocks after
+//! thresholds. The old `WORK_TOOLS` list, `is_allowed_path` allowlist, and
+//! conductor/musician role dispatch were all hardcoded Rust constants; both
+//! are now resolved from one [`CapabilitySet`], loaded from
+//! `.claude/acl/capabilities.toml` (falling back to the old hardcoded
+//! behavior if that manifest is absent). `CapabilitySet::resolve` answers
+//! both questions this hook needs in a single lookup: `Allow` means the
+//! tool+path combination is a free pass, `Deny` means it counts toward the
+//! delegation threshold, and `None` means this role has no opinion on the
+//! tool at all (so it passes through untracked) — which is how roles beyond
+//! Conductor/Musician fall out for free: a role with no capabilities
+//! assigned never hits the threshold logic.
+//!
+//! Before any of that runs, this hook also gives the shared
+//! `hook_common::rules::RuleChain` extension pipeline first say, so a
+//! project can drop in its own delegation rule as a TOML manifest under
+//! `.claude/hooks/plugins/` instead of patching this file.
 
 use anyhow::Result;
 use hook_common::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const WORK_TOOLS: &[&str] = &["Edit", "Write", "Read", "Bash", "WebFetch", "WebSearch"];
 const DELEGATION_TOOL: &str = "Task";
 
 // Thresholds for conductor
@@ -34,14 +48,23 @@ fn main() -> Result<()> {
     let tool_name = &input.tool_name;
     let tool_input = &input.tool_input;
 
-    let role = get_role();
+    let role = match detect_session_kind() {
+        SessionKind::Conductor => "conductor",
+        SessionKind::Musician => "musician",
+    };
+
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+    let capabilities = CapabilitySet::load(&CapabilitySet::default_path(Path::new(&project_dir)), role);
 
-    // Musicians have no restrictions
-    if role == "musician" {
+    // Give any project-specific rule extensions (see `hook_common::rules`)
+    // first say over the built-in delegation logic below.
+    let rule_dir = RuleChain::default_dir(Path::new(&project_dir));
+    if let Some(output) = RuleChain::discover(&rule_dir).evaluate(&input, "PreToolUse") {
+        output.write_stdout()?;
         return Ok(());
     }
 
-    let state_file = state_path(&role);
+    let state_file = state_path(role);
     let mut state = load_state(&state_file);
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -55,27 +78,18 @@ fn main() -> Result<()> {
     }
 
     // Handle delegation (Task tool with proper hierarchy)
-    if tool_name == DELEGATION_TOOL {
-        if is_delegation_from_tool_input(tool_input) {
-            state.last_delegation_ts = now;
-            state.non_delegate_count = 0;
-            state.window_start_ts = now;
-            save_state(&state_file, &state);
-            return Ok(());
-        }
+    if tool_name == DELEGATION_TOOL && is_delegation_from_tool_input(tool_input) {
+        state.last_delegation_ts = now;
+        state.non_delegate_count = 0;
+        state.window_start_ts = now;
+        save_state(&state_file, &state);
+        return Ok(());
     }
 
-    // Handle work tools
-    if WORK_TOOLS.contains(&tool_name.as_str()) {
-        // Check allowlist for Edit/Write/Read
-        if tool_name == "Edit" || tool_name == "Write" || tool_name == "Read" {
-            if let Some(file_path) = input.get_file_path() {
-                if is_allowed_path(file_path) {
-                    return Ok(());
-                }
-            }
-        }
-
+    // Resolve this role's effective permission for the tool (and path, if
+    // any). `Allow` passes through freely, `None` means untracked, and only
+    // `Deny` counts toward the delegation threshold.
+    if capabilities.resolve(tool_name, input.get_file_path()) == Some(PathDecision::Deny) {
         // Initialize window if needed
         if state.window_start_ts == 0 {
             state.window_start_ts = now;
@@ -105,15 +119,13 @@ fn main() -> Result<()> {
         );
 
         // Add stronger warning if approaching threshold
-        if state.non_delegate_count >= WARN_THRESHOLD {
-            if state.last_warning_at < state.non_delegate_count {
-                state.last_warning_at = state.non_delegate_count;
-                reminder = format!(
-                    "⚠ 委譲なし作業が {} 回です（{}回でブロック）。\n\
-                     Task ツールで委譲を検討してください。",
-                    state.non_delegate_count, BLOCK_THRESHOLD
-                );
-            }
+        if state.non_delegate_count >= WARN_THRESHOLD && state.last_warning_at < state.non_delegate_count {
+            state.last_warning_at = state.non_delegate_count;
+            reminder = format!(
+                "⚠ 委譲なし作業が {} 回です（{}回でブロック）。\n\
+                 Task ツールで委譲を検討してください。",
+                state.non_delegate_count, BLOCK_THRESHOLD
+            );
         }
 
         let output = HookOutput::allow().with_context(reminder);
@@ -124,32 +136,6 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn get_role() -> String {
-    // Check environment variable first
-    if let Ok(role) = std::env::var("AGENT_ROLE") {
-        let role_lower = role.to_lowercase();
-        if role_lower == "conductor" || role_lower == "musician" {
-            return role_lower;
-        }
-    }
-
-    // Check if conductor-session marker exists
-    if is_conductor_session() {
-        return "conductor".to_string();
-    }
-
-    // Safe default: musician
-    "musician".to_string()
-}
-
-fn is_conductor_session() -> bool {
-    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
-    let marker_path = PathBuf::from(project_dir)
-        .join(".claude")
-        .join(".conductor-session");
-    marker_path.exists()
-}
-
 fn state_path(role: &str) -> PathBuf {
     let session_id = std::env::var("CLAUDE_SESSION_ID")
         .unwrap_or_else(|_| std::process::id().to_string());
@@ -172,20 +158,6 @@ fn save_state(path: &PathBuf, state: &DelegationState) {
     }
 }
 
-fn is_allowed_path(file_path: &str) -> bool {
-    // .claude/ directory is always allowed
-    if file_path.contains(".claude") {
-        return true;
-    }
-    // memory/ directory is always allowed
-    if file_path.contains("memory") {
-        return true;
-    }
-    // Specific config files are allowed
-    let filename = file_path.rsplit('/').next().unwrap_or("");
-    matches!(filename, "pyproject.toml" | "settings.json" | ".gitignore")
-}
-
 fn is_delegation_from_tool_input(tool_input: &hook_common::input::ToolInput) -> bool {
     // Check for hierarchy keywords in prompt
     if let Some(prompt) = &tool_input.prompt {
@@ -205,11 +177,15 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_is_allowed_path() {
-        assert!(is_allowed_path(".claude/rules/test.md"));
-        assert!(is_allowed_path("/project/memory/events.jsonl"));
-        assert!(is_allowed_path("pyproject.toml"));
-        assert!(is_allowed_path("/project/settings.json"));
-        assert!(!is_allowed_path("src/main.rs"));
+    fn test_musician_role_is_never_tracked() {
+        let capabilities = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "musician");
+        assert_eq!(capabilities.resolve("Edit", Some("src/main.rs")), None);
+    }
+
+    #[test]
+    fn test_conductor_tracks_edit_outside_allowlist() {
+        let capabilities = CapabilitySet::load(Path::new("/nonexistent/capabilities.toml"), "conductor");
+        assert_eq!(capabilities.resolve("Edit", Some("src/main.rs")), Some(PathDecision::Deny));
+        assert_eq!(capabilities.resolve("Edit", Some(".claude/rules/test.md")), Some(PathDecision::Allow));
     }
 }
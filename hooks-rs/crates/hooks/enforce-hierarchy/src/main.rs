@@ -19,29 +19,36 @@ fn main() -> Result<()> {
 
     // Get file path
     let file_path = input.get_file_path().unwrap_or("");
-
-    // Check if this file is allowed for upper agents
-    if is_allowed_file(file_path) {
-        return Ok(());
-    }
-
-    // Determine agent role
     let role = get_agent_role();
-
-    // Musicians can edit anything
-    if role == "musician" {
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+
+    // Resolve allow/deny for this role+path from the trie-based engine
+    // (see `hook_common::paths`) instead of the old hardcoded component
+    // scan plus `role == "musician"` special case -- Musician's blanket
+    // access is just its own `**` allow rule now.
+    let engine = PathEngine::load(&PathEngine::default_path(Path::new(&project_dir)));
+    if engine.check(&role, file_path).decision == PathDecision::Allow {
         return Ok(());
     }
 
-    // Conductor should NOT directly edit implementation files
+    // Conductor should NOT directly edit implementation files, unless its
+    // ACL (see `hook_common::acl`) explicitly grants the `edit:direct`
+    // scope. An unconfigured role has no scopes, so this fails closed to
+    // the same behavior as before the ACL existed.
     if role == "conductor" {
+        let acl_dir = Acl::default_dir(Path::new(&project_dir));
+        let acl = Acl::load(&acl_dir, &role);
+        if acl.grants("edit:direct") {
+            return Ok(());
+        }
+
         let message = "⛔ 階層違反: Conductor（指揮者）は直接ファイルを編集できません。\n\n\
             【正しい方法】\n\
             Task ツールでサブエージェント（Musician）を spawn して委譲してください。\n\n\
             → 詳細: .claude/rules/agent-hierarchy.md";
 
         let output = HookOutput::deny().with_context(message);
-        output.write_stdout()?;
+        output.write_stdout_logged(tool_name)?;
     }
 
     Ok(())
@@ -55,43 +62,3 @@ fn get_agent_role() -> String {
     // Default: subagents are Musicians (safe default)
     "musician".to_string()
 }
-
-fn is_allowed_file(file_path: &str) -> bool {
-    let path = Path::new(file_path);
-
-    // Allow .claude/ config and documentation
-    for component in path.components() {
-        if component.as_os_str() == ".claude" {
-            return true;
-        }
-        if component.as_os_str() == "memory" {
-            return true;
-        }
-    }
-
-    // Allow pyproject.toml, settings files
-    if let Some(name) = path.file_name() {
-        let name_str = name.to_string_lossy();
-        if name_str == "pyproject.toml" || name_str == "settings.json" || name_str == ".gitignore"
-        {
-            return true;
-        }
-    }
-
-    false
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_is_allowed_file() {
-        assert!(is_allowed_file(".claude/rules/test.md"));
-        assert!(is_allowed_file("/project/.claude/settings.json"));
-        assert!(is_allowed_file("memory/events.jsonl"));
-        assert!(is_allowed_file("pyproject.toml"));
-        assert!(!is_allowed_file("src/main.rs"));
-        assert!(!is_allowed_file("lib/utils.py"));
-    }
-}
@@ -0,0 +1,268 @@
+//! Core decision logic for the no-merge hook, factored out of `main` so it
+//! can be driven by recorded fixtures in the `hook-replay` harness instead
+//! of only by a real stdin pipe.
+//!
+//! `gh pr ready` takes a PR out of draft, and (mirroring cranko's
+//! fragment-based release flow) that should only happen once there's an
+//! unreleased changelog fragment to ship with it. Before falling through to
+//! the rest of this hook's usual pass-through, `run` denies `gh pr ready`
+//! outright if it can't find one -- any file under the project's fragment
+//! directory (`.changeset/` for Node projects, `changelog.d/` otherwise), or
+//! a `## Unreleased` entry with content in `CHANGELOG.md`. `ALLOW_PR_READY=1`
+//! is the escape hatch for repos that don't keep a changelog.
+
+use hook_common::prelude::*;
+use regex::Regex;
+use std::path::Path;
+
+/// Denial message for a blocked merge command, `pub` so fixtures/tests
+/// (e.g. `hook-replay`) can assert against the real text instead of a
+/// paraphrase that can drift out of sync with it.
+pub const BLOCK_MESSAGE: &str = r#"⛔ マージ操作はブロックされています。
+
+【理由】
+マージはユーザーが行うべき操作です。
+
+【許可されている操作】
+- gh pr ready（レビュー準備完了にする）
+- gh pr view（PRを確認する）
+
+【マージ方法】
+GitHub UI または以下のコマンドをユーザーが実行:
+  gh pr merge <number>"#;
+
+const NO_CHANGELOG_FRAGMENT_MESSAGE: &str = r#"⛔ 未リリースの changelog フラグメントが見つかりません。
+
+【理由】
+Draft から外れる PR には、リリースノートの元になるフラグメントが必要です。
+
+【対応方法】
+- changelog.d/ に新しいフラグメントファイルを追加する、または
+- CHANGELOG.md の `## Unreleased` セクションに変更内容を追記する
+
+どうしても不要な場合は ALLOW_PR_READY=1 で回避できます。"#;
+
+/// Decide whether `input` should be blocked. `None` means silent pass.
+pub fn run(input: &HookInput) -> Option<HookOutput> {
+    if !input.is_bash() {
+        return None;
+    }
+
+    let command = input.get_command()?;
+    let config = HooksConfig::load();
+
+    if is_merge_command(command, &config.no_merge.extra_block_patterns) {
+        return Some(HookOutput::deny().with_context(BLOCK_MESSAGE));
+    }
+
+    if is_pr_ready_command(command) && std::env::var("ALLOW_PR_READY").as_deref() != Ok("1") {
+        let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+        if !has_pending_changelog_fragment(Path::new(&project_dir)) {
+            return Some(HookOutput::deny().with_context(NO_CHANGELOG_FRAGMENT_MESSAGE));
+        }
+        // A fragment exists -- fall through to whatever confirmation the
+        // rest of this hook (plugins, rules) would otherwise apply.
+    }
+
+    // Give any installed analyzer plugins (see `hook_common::plugins`) a
+    // chance to block commands this hook doesn't know about. A missing
+    // plugin directory is a silent no-op.
+    if let Some(plugin_dir) = PluginRegistry::default_dir() {
+        if let Some(output) = PluginRegistry::discover(&plugin_dir).evaluate(input) {
+            return Some(output);
+        }
+    }
+
+    // Same idea, but for lightweight in-process rules (see
+    // `hook_common::rules`) that don't warrant spawning a subprocess --
+    // a TOML manifest under `.claude/hooks/plugins/` is enough.
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+    let rule_dir = RuleChain::default_dir(std::path::Path::new(&project_dir));
+    if let Some(output) = RuleChain::discover(&rule_dir).evaluate(input, "PreToolUse") {
+        return Some(output);
+    }
+
+    None
+}
+
+/// Check if command is a merge operation, built-in patterns plus any
+/// `extra_patterns` supplied via `[no_merge] extra_block_patterns` in
+/// `~/.config/ai/hooks.toml` (see `hook_common::config`).
+pub fn is_merge_command(command: &str, extra_patterns: &[String]) -> bool {
+    // gh pr merge
+    let gh_merge = Regex::new(r"\bgh\s+pr\s+merge\b").unwrap();
+    if gh_merge.is_match(command) {
+        return true;
+    }
+
+    // git merge (but not in commit message context)
+    let git_merge = Regex::new(r"\bgit\s+merge\b").unwrap();
+    if git_merge.is_match(command) {
+        // Allow if it's clearly a commit message or echo
+        if command.contains("echo") || command.contains("-m \"") || command.contains("-m '") {
+            return false;
+        }
+        return true;
+    }
+
+    extra_patterns.iter().any(|pattern| {
+        Regex::new(pattern).map(|re| re.is_match(command)).unwrap_or(false)
+    })
+}
+
+/// Whether `command` takes a PR out of draft.
+pub fn is_pr_ready_command(command: &str) -> bool {
+    Regex::new(r"\bgh\s+pr\s+ready\b").unwrap().is_match(command)
+}
+
+/// The packaging system this project uses, detected from marker files, to
+/// pick the expected fragment directory: Cargo and Python projects in this
+/// org use cranko/towncrier-style `changelog.d/`, while Node projects use
+/// Changesets' `.changeset/` convention instead.
+#[derive(Debug, PartialEq, Eq)]
+enum PackagingSystem {
+    Cargo,
+    Python,
+    Node,
+    Unknown,
+}
+
+fn detect_packaging_system(project_dir: &Path) -> PackagingSystem {
+    if project_dir.join("Cargo.toml").exists() {
+        PackagingSystem::Cargo
+    } else if project_dir.join("pyproject.toml").exists() {
+        PackagingSystem::Python
+    } else if project_dir.join("package.json").exists() {
+        PackagingSystem::Node
+    } else {
+        PackagingSystem::Unknown
+    }
+}
+
+/// The fragment directory `has_pending_changelog_fragment` should look in
+/// for this packaging system.
+fn fragment_dir_name(packaging: &PackagingSystem) -> &'static str {
+    match packaging {
+        PackagingSystem::Node => ".changeset",
+        PackagingSystem::Cargo | PackagingSystem::Python | PackagingSystem::Unknown => "changelog.d",
+    }
+}
+
+/// Whether this project has an unreleased changelog fragment ready to ship:
+/// any file under its packaging system's fragment directory (see
+/// [`fragment_dir_name`]), or a `## Unreleased` section in `CHANGELOG.md`
+/// with at least one non-blank line under it.
+fn has_pending_changelog_fragment(project_dir: &Path) -> bool {
+    let packaging = detect_packaging_system(project_dir);
+
+    let fragment_dir = project_dir.join(fragment_dir_name(&packaging));
+    if let Ok(entries) = std::fs::read_dir(&fragment_dir) {
+        if entries.filter_map(Result::ok).any(|entry| entry.path().is_file()) {
+            return true;
+        }
+    }
+
+    std::fs::read_to_string(project_dir.join("CHANGELOG.md"))
+        .map(|contents| unreleased_section_has_content(&contents))
+        .unwrap_or(false)
+}
+
+fn unreleased_section_has_content(changelog: &str) -> bool {
+    let Some(after_heading) = changelog.split_once("## Unreleased").map(|(_, rest)| rest) else {
+        return false;
+    };
+    let section = after_heading.split("\n## ").next().unwrap_or(after_heading);
+    section.lines().any(|line| !line.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gh_pr_merge_blocked() {
+        assert!(is_merge_command("gh pr merge 123", &[]));
+        assert!(is_merge_command("gh pr merge", &[]));
+        assert!(is_merge_command("  gh  pr  merge  --auto", &[]));
+    }
+
+    #[test]
+    fn test_git_merge_blocked() {
+        assert!(is_merge_command("git merge main", &[]));
+        assert!(is_merge_command("git merge feature/branch", &[]));
+    }
+
+    #[test]
+    fn test_allowed_commands() {
+        assert!(!is_merge_command("gh pr ready", &[]));
+        assert!(!is_merge_command("gh pr view", &[]));
+        assert!(!is_merge_command("git status", &[]));
+        assert!(!is_merge_command("git commit -m \"Merge changes\"", &[]));
+    }
+
+    #[test]
+    fn test_extra_block_patterns() {
+        let extra = vec![r"\bsvn\s+merge\b".to_string()];
+        assert!(is_merge_command("svn merge -r 1:2 branch", &extra));
+        assert!(!is_merge_command("svn merge -r 1:2 branch", &[]));
+    }
+
+    #[test]
+    fn test_is_pr_ready_command() {
+        assert!(is_pr_ready_command("gh pr ready 42"));
+        assert!(!is_pr_ready_command("gh pr merge 42"));
+    }
+
+    #[test]
+    fn test_detect_packaging_system() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_packaging_system(dir.path()), PackagingSystem::Unknown);
+
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+        assert_eq!(detect_packaging_system(dir.path()), PackagingSystem::Cargo);
+    }
+
+    #[test]
+    fn test_fragment_dir_counts_as_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("changelog.d")).unwrap();
+        std::fs::write(dir.path().join("changelog.d").join("123.md"), "Added foo").unwrap();
+        assert!(has_pending_changelog_fragment(dir.path()));
+    }
+
+    #[test]
+    fn test_node_project_looks_in_changeset_dir_not_changelog_d() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        std::fs::create_dir(dir.path().join("changelog.d")).unwrap();
+        std::fs::write(dir.path().join("changelog.d").join("123.md"), "Added foo").unwrap();
+        // A Node project doesn't use changelog.d/, so a fragment left there
+        // shouldn't count...
+        assert!(!has_pending_changelog_fragment(dir.path()));
+
+        // ...but one under .changeset/ should.
+        std::fs::create_dir(dir.path().join(".changeset")).unwrap();
+        std::fs::write(dir.path().join(".changeset").join("brave-foxes-jump.md"), "Added foo").unwrap();
+        assert!(has_pending_changelog_fragment(dir.path()));
+    }
+
+    #[test]
+    fn test_empty_unreleased_section_is_not_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n\n## Unreleased\n\n## 1.0.0\n- old\n").unwrap();
+        assert!(!has_pending_changelog_fragment(dir.path()));
+    }
+
+    #[test]
+    fn test_unreleased_section_with_content_is_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("CHANGELOG.md"), "# Changelog\n\n## Unreleased\n- Added foo\n\n## 1.0.0\n- old\n").unwrap();
+        assert!(has_pending_changelog_fragment(dir.path()));
+    }
+
+    #[test]
+    fn test_missing_changelog_is_not_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!has_pending_changelog_fragment(dir.path()));
+    }
+}
@@ -1,10 +1,12 @@
 //! Ensure PR is open before allowing Edit/Write.
 //!
-//! Blocks file modifications if no open PR exists.
+//! Blocks file modifications if no open PR exists. Open-PR status is
+//! looked up through `hook_common::vcs_cache` rather than spawning `gh`
+//! directly, since many Edit/Write calls in one session ask this same
+//! question.
 
 use anyhow::Result;
 use hook_common::prelude::*;
-use hook_common::subprocess::gh;
 
 const BLOCK_MESSAGE: &str = r#"⛔ 編集をブロック: オープンなPRがありません。
 
@@ -16,21 +18,6 @@ const BLOCK_MESSAGE: &str = r#"⛔ 編集をブロック: オープンなPRが
 
 または新しいセッションを開始してください。"#;
 
-/// Check if there's any open PR for the current repository.
-fn has_any_open_pr() -> bool {
-    match gh("pr list --state open --json number") {
-        Ok(result) if result.success => {
-            // Parse JSON array
-            if let Ok(prs) = serde_json::from_str::<Vec<serde_json::Value>>(&result.stdout) {
-                !prs.is_empty()
-            } else {
-                false
-            }
-        }
-        _ => false,
-    }
-}
-
 fn main() -> Result<()> {
     let input = HookInput::from_stdin()?;
 
@@ -39,8 +26,10 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+
     // Check if any PR is open
-    if has_any_open_pr() {
+    if has_any_open_pr(&project_dir) {
         return Ok(());
     }
 
@@ -50,14 +39,3 @@ fn main() -> Result<()> {
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_has_open_pr_returns_bool() {
-        // Just verify the function doesn't panic
-        let _ = has_any_open_pr();
-    }
-}
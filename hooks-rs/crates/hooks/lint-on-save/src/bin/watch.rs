@@ -0,0 +1,117 @@
+//! Standalone watch-mode daemon for the lint-on-save checks.
+//!
+//! The `lint-on-save` hook only fires when Claude invokes Edit/Write, so
+//! edits made by hand or by another tool go unchecked. This binary polls
+//! the project tree directly, debouncing rapid saves into batches, and
+//! runs the same `LintRegistry` pipeline the hook uses for every file
+//! extension it has commands configured for.
+//!
+//! The project directory is re-resolved from `CLAUDE_PROJECT_DIR` on every
+//! batch rather than once at startup, so moving or recreating the project
+//! directory mid-run doesn't leave the watcher pointed at a stale path.
+
+use anyhow::Result;
+use camino::Utf8PathBuf;
+use ignore::WalkBuilder;
+use lint_on_save::LintRegistry;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// Poll/debounce interval: rapid saves within this window are coalesced
+/// into a single batch.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+fn main() -> Result<()> {
+    // `None` until the first scan establishes a baseline; the first batch
+    // of eligible files is never linted, only recorded, so startup doesn't
+    // trigger a full-project check.
+    let mut known: Option<HashMap<Utf8PathBuf, SystemTime>> = None;
+
+    eprintln!("[lint-on-save] watching for changes (Ctrl-C to stop)");
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        // Re-resolve fresh every batch: don't cache a directory handle or
+        // registry, so `.claude/lint.toml` edits and project moves both
+        // take effect on the next scan.
+        let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+        let registry = LintRegistry::load(&project_dir);
+        let current = scan_eligible_files(&project_dir, &registry);
+
+        let baseline = match known.take() {
+            Some(baseline) => baseline,
+            None => {
+                known = Some(current);
+                continue;
+            }
+        };
+
+        let mut next_known = current.clone();
+
+        for (path, mtime) in &current {
+            if baseline.get(path) == Some(mtime) {
+                continue;
+            }
+
+            eprintln!("[lint-on-save] change detected: {}", path);
+            let issues = registry
+                .check_file(&project_dir, path.as_str())
+                .unwrap_or_default();
+            if issues.is_empty() {
+                eprintln!("[lint-on-save] OK: {} passed all checks", path);
+            } else {
+                eprintln!(
+                    "[lint-on-save] Issues in {}:\n{}",
+                    path,
+                    issues.join("\n")
+                );
+            }
+
+            // check_file may itself rewrite the file (ruff format/--fix).
+            // Record the post-check mtime as the new baseline so that
+            // formatter-induced write is not mistaken for an external edit
+            // on the next scan.
+            if let Ok(mtime) = std::fs::metadata(path).and_then(|m| m.modified()) {
+                next_known.insert(path.clone(), mtime);
+            }
+        }
+
+        known = Some(next_known);
+    }
+}
+
+/// Walk `project_dir`, honoring `.gitignore`, and return the last-modified
+/// time of every file whose extension `registry` has commands configured
+/// for.
+fn scan_eligible_files(
+    project_dir: &str,
+    registry: &LintRegistry,
+) -> HashMap<Utf8PathBuf, SystemTime> {
+    let mut files = HashMap::new();
+
+    for entry in WalkBuilder::new(project_dir).hidden(true).build() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+
+        let Ok(path) = Utf8PathBuf::from_path_buf(entry.into_path()) else {
+            continue; // skip non-UTF-8 paths
+        };
+        let Some(extension) = path.extension() else {
+            continue;
+        };
+        if registry.commands_for(extension).is_none() {
+            continue;
+        }
+
+        let Ok(mtime) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+            continue;
+        };
+
+        files.insert(path, mtime);
+    }
+
+    files
+}
@@ -0,0 +1,21 @@
+//! Shared checking logic for the lint-on-save hook and its `watch` daemon.
+//!
+//! Dispatches to the right formatter/linter/typechecker pipeline for a
+//! file's extension via `LintRegistry` (see `registry`), so both `main`
+//! (the PostToolUse hook) and the `watch` binary (a standalone daemon for
+//! edits made outside Claude) cover the same polyglot set of languages.
+
+pub mod registry;
+
+pub use registry::LintRegistry;
+
+/// Run the configured checks for `file_path` under `project_dir`, loading
+/// any `.claude/lint.toml` overrides fresh each call. Returns an empty list
+/// for both "passed all checks" and "extension unsupported" — callers that
+/// need to skip cleanly on unsupported extensions should check
+/// `LintRegistry::load(project_dir).commands_for(..)` themselves first.
+pub fn check_file(project_dir: &str, file_path: &str) -> Vec<String> {
+    LintRegistry::load(project_dir)
+        .check_file(project_dir, file_path)
+        .unwrap_or_default()
+}
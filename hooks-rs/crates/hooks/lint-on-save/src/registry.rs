@@ -0,0 +1,181 @@
+//! Language-agnostic formatter/linter dispatch table.
+//!
+//! Maps a file extension to an ordered list of command templates (format,
+//! lint+fix, typecheck, ...). Ships a default table covering Python, Rust,
+//! and JS/TS, and can be overridden per-project via `.claude/lint.toml`
+//! (same extension keys, each mapping to a `commands` list).
+
+use hook_common::subprocess::{run_command_sandboxed, Limits};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Placeholder substituted with the file's path in each command template.
+const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Ordered list of command templates for one extension.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LintEntry {
+    pub commands: Vec<String>,
+}
+
+/// Extension -> `LintEntry` dispatch table.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LintRegistry {
+    #[serde(flatten)]
+    extensions: HashMap<String, LintEntry>,
+}
+
+impl Default for LintRegistry {
+    fn default() -> Self {
+        let mut extensions = HashMap::new();
+
+        extensions.insert(
+            "py".to_string(),
+            LintEntry {
+                commands: vec![
+                    "uv run ruff format {file}".to_string(),
+                    "uv run ruff check --fix {file}".to_string(),
+                    "uv run ty check {file}".to_string(),
+                ],
+            },
+        );
+        extensions.insert(
+            "rs".to_string(),
+            LintEntry {
+                commands: vec![
+                    "rustfmt {file}".to_string(),
+                    "cargo clippy --fix --allow-dirty --allow-staged -- -D warnings".to_string(),
+                ],
+            },
+        );
+
+        let js_entry = LintEntry {
+            commands: vec![
+                "npx prettier --write {file}".to_string(),
+                "npx eslint --fix {file}".to_string(),
+            ],
+        };
+        extensions.insert("js".to_string(), js_entry.clone());
+        extensions.insert("jsx".to_string(), js_entry.clone());
+        extensions.insert("ts".to_string(), js_entry.clone());
+        extensions.insert("tsx".to_string(), js_entry);
+
+        Self { extensions }
+    }
+}
+
+impl LintRegistry {
+    /// Load the default table, with entries overridden by
+    /// `.claude/lint.toml` under `project_dir` where present. Missing or
+    /// unparsable config falls back to the default table unchanged.
+    pub fn load(project_dir: &str) -> Self {
+        let mut registry = Self::default();
+
+        let config_path = Path::new(project_dir).join(".claude").join("lint.toml");
+        let Ok(contents) = std::fs::read_to_string(&config_path) else {
+            return registry;
+        };
+        let Ok(overrides) = toml::from_str::<LintRegistry>(&contents) else {
+            return registry;
+        };
+
+        registry.extensions.extend(overrides.extensions);
+        registry
+    }
+
+    /// Commands configured for `extension` (without the leading dot), or
+    /// `None` if unsupported.
+    pub fn commands_for(&self, extension: &str) -> Option<&[String]> {
+        self.extensions.get(extension).map(|e| e.commands.as_slice())
+    }
+
+    /// Run every configured command for `file_path`'s extension against it,
+    /// collecting failures into a flat issue list. Returns `None` if the
+    /// extension isn't supported, so callers can skip cleanly the same way
+    /// the hook did when it only understood `.py`.
+    pub fn check_file(&self, project_dir: &str, file_path: &str) -> Option<Vec<String>> {
+        let extension = Path::new(file_path).extension()?.to_str()?;
+        let commands = self.commands_for(extension)?;
+
+        let mut issues = Vec::new();
+        for template in commands {
+            let cmd = template.replace(FILE_PLACEHOLDER, file_path);
+            let full_cmd = format!("cd {} && {}", project_dir, cmd);
+            // Project-configured linters are arbitrary commands from
+            // `.claude/lint.toml`; sandbox them so a misbehaving one can't
+            // run away with the host's CPU/memory/fds.
+            if let Ok(result) = run_command_sandboxed(&full_cmd, Limits::new().timeout(TIMEOUT)) {
+                if result.success {
+                    continue;
+                }
+                let output = if !result.stdout.is_empty() {
+                    &result.stdout
+                } else {
+                    &result.stderr
+                };
+                // Skip silently if the tool itself isn't installed, the
+                // way `ty` already was before this was generalized.
+                if output.contains("not found") || output.contains("Failed to spawn") {
+                    continue;
+                }
+                if !output.trim().is_empty() {
+                    issues.push(format!("{} issues:\n{}", template, output));
+                }
+            }
+        }
+
+        Some(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_covers_py_rs_ts() {
+        let registry = LintRegistry::default();
+        assert!(registry.commands_for("py").is_some());
+        assert!(registry.commands_for("rs").is_some());
+        assert!(registry.commands_for("ts").is_some());
+        assert!(registry.commands_for("tsx").is_some());
+    }
+
+    #[test]
+    fn test_unsupported_extension_is_none() {
+        let registry = LintRegistry::default();
+        assert!(registry.commands_for("bin").is_none());
+    }
+
+    #[test]
+    fn test_load_falls_back_without_config() {
+        let registry = LintRegistry::load("/nonexistent/project/dir");
+        assert!(registry.commands_for("py").is_some());
+    }
+
+    #[test]
+    fn test_load_overrides_extension_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        std::fs::write(
+            dir.path().join(".claude").join("lint.toml"),
+            r#"
+            [py]
+            commands = ["custom-formatter {file}"]
+            "#,
+        )
+        .unwrap();
+
+        let registry = LintRegistry::load(dir.path().to_str().unwrap());
+        assert_eq!(
+            registry.commands_for("py"),
+            Some(&["custom-formatter {file}".to_string()][..])
+        );
+        // Untouched extensions keep their defaults.
+        assert!(registry.commands_for("rs").is_some());
+    }
+}
@@ -1,33 +1,35 @@
 //! UserPromptSubmit hook: Load relevant memories at session start.
 //!
-//! Injects relevant memories (preferences, workflows, recent errors)
-//! into the conversation context to guide behavior.
+//! Ranks stored memories against the incoming user prompt with BM25 +
+//! recency (see `hook_memory::rank`) and injects the top matches into the
+//! conversation context to guide behavior.
 
 use anyhow::Result;
+use hook_common::fs::{Fs, RealFs};
 use hook_common::prelude::*;
 use camino::Utf8PathBuf;
-use hook_memory::{MemoryStorage, MemoryType};
+use hook_memory::{rank_memories, MemoryScope, MemoryStorage, MemoryType, RankedMemory};
 use std::collections::HashMap;
-use std::fs;
 use std::path::PathBuf;
 
+// Top-K memories to inject.
+const TOP_K: usize = 5;
+
 fn main() -> Result<()> {
-    let _input = HookInput::from_stdin()?;
+    let input = HookInput::from_stdin()?;
+    let fs = RealFs;
 
     // Check if we've already loaded memories this session
     let state_file = get_state_file();
-    if state_file.exists() {
+    if already_loaded(&fs, &state_file) {
         return Ok(());
     }
+    mark_loaded(&fs, &state_file);
 
-    // Mark as loaded
-    if let Some(parent) = state_file.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    let _ = fs::write(&state_file, "loaded");
+    let prompt = input.user_prompt.as_deref().unwrap_or("");
 
     // Get relevant memories
-    let memories = get_relevant_memories();
+    let memories = get_relevant_memories(prompt);
 
     if memories.is_empty() {
         return Ok(());
@@ -48,7 +50,24 @@ fn get_state_file() -> PathBuf {
     PathBuf::from("/tmp").join(format!("claude-memory-loaded-{}.flag", session_id))
 }
 
-fn get_relevant_memories() -> Vec<MemoryEntry> {
+/// Whether memories were already injected for this session.
+fn already_loaded(fs: &dyn Fs, state_file: &std::path::Path) -> bool {
+    fs.exists(state_file)
+}
+
+/// Mark memories as loaded for this session, so a later UserPromptSubmit
+/// in the same session doesn't re-inject them.
+fn mark_loaded(fs: &dyn Fs, state_file: &std::path::Path) {
+    if let Some(parent) = state_file.parent() {
+        let _ = fs.create_dir_all(parent);
+    }
+    let _ = fs.write(state_file, "loaded");
+}
+
+/// Load and rank memories against `prompt`, restricted to user-scope
+/// memories (project-scoped memories live elsewhere and aren't relevant to
+/// this session-start injection).
+fn get_relevant_memories(prompt: &str) -> Vec<RankedMemory> {
     let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
     let storage_path = Utf8PathBuf::from(&project_dir)
         .join(".claude")
@@ -57,51 +76,24 @@ fn get_relevant_memories() -> Vec<MemoryEntry> {
 
     let storage = MemoryStorage::new(storage_path);
 
-    let mut memories = Vec::new();
+    let events = match storage.load_all() {
+        Ok(events) => events,
+        Err(_) => return Vec::new(),
+    };
 
-    // Get user preferences
-    if let Ok(prefs) = storage.load_by_type(MemoryType::Preference) {
-        for event in prefs.into_iter().take(5) {
-            memories.push(MemoryEntry {
-                content: event.content,
-                memory_type: "preference".to_string(),
-            });
-        }
-    }
-
-    // Get workflows
-    if let Ok(workflows) = storage.load_by_type(MemoryType::Workflow) {
-        for event in workflows.into_iter().take(3) {
-            memories.push(MemoryEntry {
-                content: event.content,
-                memory_type: "workflow".to_string(),
-            });
-        }
-    }
+    let user_scoped: Vec<_> = events
+        .into_iter()
+        .filter(|e| e.scope == MemoryScope::User)
+        .collect();
 
-    // Get recent errors
-    if let Ok(errors) = storage.load_by_type(MemoryType::Error) {
-        for event in errors.into_iter().take(3) {
-            memories.push(MemoryEntry {
-                content: event.content,
-                memory_type: "error".to_string(),
-            });
-        }
+    if prompt.is_empty() {
+        return Vec::new();
     }
 
-    // Dedupe by content
-    let mut seen = std::collections::HashSet::new();
-    memories.retain(|m| seen.insert(m.content.clone()));
-
-    memories
-}
-
-struct MemoryEntry {
-    content: String,
-    memory_type: String,
+    rank_memories(&user_scoped, prompt, TOP_K)
 }
 
-fn format_memories_for_context(memories: &[MemoryEntry]) -> String {
+fn format_memories_for_context(memories: &[RankedMemory]) -> String {
     if memories.is_empty() {
         return String::new();
     }
@@ -109,23 +101,20 @@ fn format_memories_for_context(memories: &[MemoryEntry]) -> String {
     let mut lines = vec!["# 記憶から読み込んだ情報\n".to_string()];
 
     // Group by type
-    let mut by_type: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut by_type: HashMap<MemoryType, Vec<&str>> = HashMap::new();
     for m in memories {
-        by_type
-            .entry(&m.memory_type)
-            .or_default()
-            .push(&m.content);
+        by_type.entry(m.event.memory_type).or_default().push(&m.event.content);
     }
 
     let type_labels = [
-        ("preference", "ユーザーの好み"),
-        ("workflow", "ワークフロー"),
-        ("error", "過去のエラーパターン"),
-        ("decision", "設計判断"),
+        (MemoryType::Preference, "ユーザーの好み"),
+        (MemoryType::Workflow, "ワークフロー"),
+        (MemoryType::Error, "過去のエラーパターン"),
+        (MemoryType::Decision, "設計判断"),
     ];
 
     for (mtype, label) in type_labels {
-        if let Some(contents) = by_type.get(mtype) {
+        if let Some(contents) = by_type.get(&mtype) {
             lines.push(format!("\n## {}\n", label));
             for content in contents {
                 lines.push(format!("- {}", content));
@@ -140,22 +129,39 @@ fn format_memories_for_context(memories: &[MemoryEntry]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hook_memory::{AgentType, MemoryEvent};
+
+    fn ranked(content: &str, memory_type: MemoryType, score: f64) -> RankedMemory {
+        RankedMemory {
+            event: MemoryEvent::new(content, memory_type, MemoryScope::User, AgentType::System),
+            score,
+        }
+    }
 
     #[test]
     fn test_format_memories() {
         let memories = vec![
-            MemoryEntry {
-                content: "PRは日本語で書く".to_string(),
-                memory_type: "preference".to_string(),
-            },
-            MemoryEntry {
-                content: "テスト先に書く".to_string(),
-                memory_type: "workflow".to_string(),
-            },
+            ranked("PRは日本語で書く", MemoryType::Preference, 1.0),
+            ranked("テスト先に書く", MemoryType::Workflow, 0.9),
         ];
 
         let context = format_memories_for_context(&memories);
         assert!(context.contains("ユーザーの好み"));
         assert!(context.contains("PRは日本語で書く"));
     }
+
+    #[test]
+    fn test_empty_prompt_yields_no_memories() {
+        assert!(get_relevant_memories("").is_empty());
+    }
+
+    #[test]
+    fn test_mark_loaded_then_already_loaded_on_fake_fs() {
+        let fs = hook_common::fs::FakeFs::new();
+        let state_file = PathBuf::from("/tmp/claude-memory-loaded-test.flag");
+
+        assert!(!already_loaded(&fs, &state_file));
+        mark_loaded(&fs, &state_file);
+        assert!(already_loaded(&fs, &state_file));
+    }
 }
@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use hook_common::prelude::*;
+use hook_common::subprocess::Plugin;
 use regex::Regex;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
@@ -57,8 +58,15 @@ fn main() -> Result<()> {
         "output": truncate_text(&output, 5000),
     });
 
-    // Log to file
-    log_entry(&entry)?;
+    // Log to file, or to a pluggable external logger if one is configured
+    // (CLAUDE_LOGGER_PLUGIN=/path/to/logger speaking the hook_common JSON-RPC
+    // plugin protocol). This lets users swap in their own logging backend
+    // without recompiling this crate.
+    if let Ok(plugin_path) = std::env::var("CLAUDE_LOGGER_PLUGIN") {
+        log_entry_via_plugin(&plugin_path, &entry)?;
+    } else {
+        log_entry(&entry)?;
+    }
 
     // Return context
     let context = format!(
@@ -150,6 +158,26 @@ fn log_entry(entry: &serde_json::Value) -> Result<()> {
     Ok(())
 }
 
+/// Forward a log entry to an external logger plugin instead of the local
+/// JSONL file. Falls back to the local file if the plugin can't be reached,
+/// so a misbehaving plugin never drops a log entry.
+fn log_entry_via_plugin(plugin_path: &str, entry: &serde_json::Value) -> Result<()> {
+    let mut plugin = match Plugin::spawn(plugin_path) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Warning: Failed to spawn logger plugin {}: {}", plugin_path, e);
+            return log_entry(entry);
+        }
+    };
+
+    if let Err(e) = plugin.call("log", entry.clone()) {
+        eprintln!("Warning: Logger plugin call failed: {}", e);
+        return log_entry(entry);
+    }
+
+    plugin.shutdown()
+}
+
 /// Get log directory.
 fn get_log_dir() -> PathBuf {
     if let Ok(project_dir) = std::env::var("CLAUDE_PROJECT_DIR") {
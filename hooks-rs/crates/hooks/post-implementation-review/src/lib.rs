@@ -0,0 +1,323 @@
+//! Core decision logic for the review-suggestion hook, factored out of
+//! `main` so it can be driven by recorded fixtures in the `hook-replay`
+//! harness instead of only by a real stdin pipe.
+//!
+//! Tracks file changes per monorepo target (see `targets`) and suggests
+//! code review when a single target accumulates substantial churn. Churn
+//! is measured by diffing the written content against the file's `HEAD`
+//! revision (falling back to raw line counting for untracked files), so
+//! edits that touch existing code aren't double counted against the whole
+//! file's size.
+
+mod targets;
+
+use hook_common::prelude::*;
+use hook_common::subprocess::run_command;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use targets::TargetRegistry;
+
+// State file to track changes in this session
+fn state_file() -> PathBuf {
+    PathBuf::from("/tmp/claude-code-implementation-state.json")
+}
+
+// Thresholds for suggesting review (defaults; overridable via
+// `[review] min_files_for_review` / `min_lines_for_review` in
+// `~/.config/ai/hooks.toml`, see `hook_common::config`)
+const MIN_FILES_FOR_REVIEW: usize = 3;
+const MIN_LINES_FOR_REVIEW: usize = 100;
+
+// Source file extensions
+const SOURCE_EXTENSIONS: &[&str] = &[".py", ".ts", ".js", ".tsx", ".jsx", ".go", ".rs"];
+
+/// Per-target tally of churn within this session.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct TargetState {
+    files_changed: Vec<String>,
+    total_lines: usize,
+    review_suggested: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ImplementationState {
+    targets: HashMap<String, TargetState>,
+}
+
+/// Record `input`'s change and decide whether to suggest review. `None`
+/// means silent pass (no Write/Edit, non-source file, or no target
+/// crossed its threshold yet).
+pub fn run(input: &HookInput) -> Option<HookOutput> {
+    let tool_name = &input.tool_name;
+
+    // Only process Write/Edit tools
+    if tool_name != "Write" && tool_name != "Edit" {
+        return None;
+    }
+
+    let file_path = input.get_file_path().unwrap_or("");
+    let content = input.tool_input.content.as_deref().unwrap_or("");
+
+    // Validate input
+    if file_path.is_empty() || file_path.len() > 4096 || file_path.contains("..") {
+        return None;
+    }
+
+    // Skip non-source files
+    if !SOURCE_EXTENSIONS.iter().any(|ext| file_path.ends_with(ext)) {
+        return None;
+    }
+
+    let project_dir = std::env::var("CLAUDE_PROJECT_DIR").unwrap_or_else(|_| ".".to_string());
+    let registry = TargetRegistry::load(&project_dir);
+    let target = registry.target_for(file_path);
+
+    // Load and update state
+    let mut state = load_state();
+    let target_state = state.targets.entry(target.clone()).or_default();
+
+    if !target_state.files_changed.contains(&file_path.to_string()) {
+        target_state.files_changed.push(file_path.to_string());
+    }
+    target_state.total_lines += added_lines(&project_dir, file_path, content);
+    save_state(&state);
+
+    // Check if review should be suggested for any target
+    let config = HooksConfig::load();
+    let min_files = config.review.min_files_for_review.unwrap_or(MIN_FILES_FOR_REVIEW);
+    let min_lines = config.review.min_lines_for_review.unwrap_or(MIN_LINES_FOR_REVIEW);
+    let flagged = should_suggest_review(&state, min_files, min_lines);
+    if flagged.is_empty() {
+        return None;
+    }
+
+    for (target, _) in &flagged {
+        if let Some(target_state) = state.targets.get_mut(target) {
+            target_state.review_suggested = true;
+        }
+    }
+    save_state(&state);
+
+    let context = format_review_context(&flagged);
+    Some(HookOutput::post_tool_use().with_context(context))
+}
+
+fn load_state() -> ImplementationState {
+    fs::read_to_string(state_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &ImplementationState) {
+    if let Ok(content) = serde_json::to_string(state) {
+        let _ = fs::write(state_file(), content);
+    }
+}
+
+fn count_lines(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#') && !trimmed.starts_with("//")
+        })
+        .count()
+}
+
+/// Classification of a line when comparing two revisions of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineDiff {
+    Added,
+    Removed,
+    Matching,
+}
+
+/// Merge-join two sorted line lists (the baseline and the new content),
+/// the way a revision-to-revision status is built from two manifests:
+/// walk both in lockstep, classifying each line as present in both
+/// (`Matching`), only in the new revision (`Added`), or only in the old
+/// one (`Removed`).
+fn diff_lines(old_lines: &[&str], new_lines: &[&str]) -> Vec<LineDiff> {
+    let mut old_sorted = old_lines.to_vec();
+    let mut new_sorted = new_lines.to_vec();
+    old_sorted.sort_unstable();
+    new_sorted.sort_unstable();
+
+    let mut diffs = Vec::with_capacity(old_sorted.len().max(new_sorted.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old_sorted.len() && j < new_sorted.len() {
+        match old_sorted[i].cmp(new_sorted[j]) {
+            std::cmp::Ordering::Equal => {
+                diffs.push(LineDiff::Matching);
+                i += 1;
+                j += 1;
+            }
+            std::cmp::Ordering::Less => {
+                diffs.push(LineDiff::Removed);
+                i += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                diffs.push(LineDiff::Added);
+                j += 1;
+            }
+        }
+    }
+    diffs.extend(std::iter::repeat(LineDiff::Removed).take(old_sorted.len() - i));
+    diffs.extend(std::iter::repeat(LineDiff::Added).take(new_sorted.len() - j));
+    diffs
+}
+
+/// Lines added by this write, computed by diffing `new_content` against
+/// the file's `HEAD` revision (removed lines are not subtracted). Falls back to [`count_lines`] on
+/// `new_content` alone when the file is untracked or git is unavailable.
+fn added_lines(project_dir: &str, file_path: &str, new_content: &str) -> usize {
+    let Some(baseline) = git_show_head(project_dir, file_path) else {
+        return count_lines(new_content);
+    };
+
+    let old_lines: Vec<&str> = baseline.lines().collect();
+    let new_lines: Vec<&str> = new_content.lines().collect();
+
+    diff_lines(&old_lines, &new_lines)
+        .iter()
+        .filter(|d| **d == LineDiff::Added)
+        .count()
+}
+
+/// Fetch `file_path` as it exists at `HEAD`, relative to `project_dir`.
+/// `None` if the file is untracked or not under git.
+fn git_show_head(project_dir: &str, file_path: &str) -> Option<String> {
+    let rel_path = file_path
+        .strip_prefix(project_dir)
+        .unwrap_or(file_path)
+        .trim_start_matches('/');
+
+    let result = run_command(&format!(
+        "cd {} && git show HEAD:{}",
+        project_dir, rel_path
+    ))
+    .ok()?;
+
+    if !result.success {
+        return None;
+    }
+    Some(result.stdout)
+}
+
+/// Targets that cross a review threshold and haven't already been
+/// flagged, paired with the reason.
+fn should_suggest_review(
+    state: &ImplementationState,
+    min_files: usize,
+    min_lines: usize,
+) -> Vec<(String, String)> {
+    let mut flagged = Vec::new();
+
+    for (target, target_state) in &state.targets {
+        if target_state.review_suggested {
+            continue;
+        }
+
+        let files_count = target_state.files_changed.len();
+        if files_count >= min_files {
+            flagged.push((target.clone(), format!("{} files modified", files_count)));
+        } else if target_state.total_lines >= min_lines {
+            flagged.push((target.clone(), format!("{}+ lines written", target_state.total_lines)));
+        }
+    }
+
+    flagged.sort();
+    flagged
+}
+
+fn format_review_context(flagged: &[(String, String)]) -> String {
+    let targets_summary = flagged
+        .iter()
+        .map(|(target, reason)| format!("`{}` ({})", target, reason))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "[Code Review Suggestion] Significant changes in {}. \
+         Consider having Codex review the implementation. \
+         **Recommended**: Use Task tool with subagent_type='general-purpose' \
+         to consult Codex with git diff scoped to the affected target(s) and preserve main context.",
+        targets_summary
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines("line1\nline2\n\n# comment"), 2);
+        assert_eq!(count_lines("// comment\ncode\n"), 1);
+    }
+
+    #[test]
+    fn test_diff_lines_classifies_added_removed_matching() {
+        let old = vec!["a", "b", "c"];
+        let new = vec!["a", "c", "d"];
+        let diffs = diff_lines(&old, &new);
+
+        let added = diffs.iter().filter(|d| **d == LineDiff::Added).count();
+        let removed = diffs.iter().filter(|d| **d == LineDiff::Removed).count();
+        let matching = diffs.iter().filter(|d| **d == LineDiff::Matching).count();
+
+        assert_eq!(added, 1); // "d"
+        assert_eq!(removed, 1); // "b"
+        assert_eq!(matching, 2); // "a", "c"
+    }
+
+    #[test]
+    fn test_diff_lines_empty_old_is_all_added() {
+        let new = vec!["x", "y"];
+        let diffs = diff_lines(&[], &new);
+        assert!(diffs.iter().all(|d| *d == LineDiff::Added));
+    }
+
+    fn target_state(files: &[&str], total_lines: usize) -> TargetState {
+        TargetState {
+            files_changed: files.iter().map(|s| s.to_string()).collect(),
+            total_lines,
+            review_suggested: false,
+        }
+    }
+
+    #[test]
+    fn test_should_suggest_review_per_target() {
+        let mut state = ImplementationState::default();
+        state.targets.insert(
+            "services/api".to_string(),
+            target_state(&["a.py", "b.py", "c.py"], 50),
+        );
+        state.targets.insert("libs/auth".to_string(), target_state(&["x.py"], 10));
+
+        let flagged = should_suggest_review(&state, MIN_FILES_FOR_REVIEW, MIN_LINES_FOR_REVIEW);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].0, "services/api");
+    }
+
+    #[test]
+    fn test_should_suggest_review_skips_already_flagged() {
+        let mut state = ImplementationState::default();
+        let mut already = target_state(&["a.py", "b.py", "c.py"], 50);
+        already.review_suggested = true;
+        state.targets.insert("services/api".to_string(), already);
+
+        assert!(should_suggest_review(&state, MIN_FILES_FOR_REVIEW, MIN_LINES_FOR_REVIEW).is_empty());
+    }
+
+    #[test]
+    fn test_should_suggest_review_respects_custom_thresholds() {
+        let mut state = ImplementationState::default();
+        state.targets.insert("libs/auth".to_string(), target_state(&["x.py"], 10));
+
+        assert!(should_suggest_review(&state, 1, MIN_LINES_FOR_REVIEW)[0].0 == "libs/auth");
+    }
+}
@@ -0,0 +1,102 @@
+//! Maps a changed file path to the monorepo target/project that owns it.
+//!
+//! Target roots (e.g. `services/api`, `libs/auth`) are loaded into a
+//! prefix trie (`trie_rs::TrieBuilder`) keyed by path component, so a
+//! changed file resolves to its owning target via longest-prefix lookup.
+//! Files under no configured root fall into the `"root"` bucket.
+
+use serde::Deserialize;
+use std::path::Path;
+use trie_rs::{Trie, TrieBuilder};
+
+/// Bucket name for files that don't fall under any configured target root.
+pub const DEFAULT_TARGET: &str = "root";
+
+#[derive(Debug, Deserialize, Default)]
+struct TargetsConfig {
+    #[serde(default)]
+    roots: Vec<String>,
+}
+
+pub struct TargetRegistry {
+    trie: Trie<String>,
+}
+
+impl TargetRegistry {
+    pub fn new(roots: &[String]) -> Self {
+        let mut builder = TrieBuilder::new();
+        for root in roots {
+            builder.push(components(root));
+        }
+        Self {
+            trie: builder.build(),
+        }
+    }
+
+    /// Load target roots from `.claude/targets.toml` under `project_dir`
+    /// (a `roots = ["services/api", ...]` list). Missing or unparsable
+    /// config yields an empty registry, so every file falls into
+    /// [`DEFAULT_TARGET`].
+    pub fn load(project_dir: &str) -> Self {
+        let config_path = Path::new(project_dir).join(".claude").join("targets.toml");
+        let roots = std::fs::read_to_string(&config_path)
+            .ok()
+            .and_then(|contents| toml::from_str::<TargetsConfig>(&contents).ok())
+            .map(|config| config.roots)
+            .unwrap_or_default();
+
+        Self::new(&roots)
+    }
+
+    /// Resolve `file_path` to its owning target via longest-prefix match
+    /// against the configured roots, or [`DEFAULT_TARGET`] if none match.
+    pub fn target_for(&self, file_path: &str) -> String {
+        let path_components = components(file_path);
+        self.trie
+            .common_prefix_search(path_components)
+            .max_by_key(|m: &Vec<String>| m.len())
+            .map(|m| m.join("/"))
+            .unwrap_or_else(|| DEFAULT_TARGET.to_string())
+    }
+}
+
+fn components(path: &str) -> Vec<String> {
+    path.split('/').filter(|s| !s.is_empty()).map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let registry = TargetRegistry::new(&["services".to_string(), "services/api".to_string()]);
+        assert_eq!(registry.target_for("services/api/handler.rs"), "services/api");
+    }
+
+    #[test]
+    fn test_no_match_falls_back_to_default() {
+        let registry = TargetRegistry::new(&["services/api".to_string()]);
+        assert_eq!(registry.target_for("tools/scripts/run.py"), DEFAULT_TARGET);
+    }
+
+    #[test]
+    fn test_load_falls_back_without_config() {
+        let registry = TargetRegistry::load("/nonexistent/project/dir");
+        assert_eq!(registry.target_for("anything/here.rs"), DEFAULT_TARGET);
+    }
+
+    #[test]
+    fn test_load_reads_roots_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        std::fs::write(
+            dir.path().join(".claude").join("targets.toml"),
+            r#"roots = ["libs/auth"]"#,
+        )
+        .unwrap();
+
+        let registry = TargetRegistry::load(dir.path().to_str().unwrap());
+        assert_eq!(registry.target_for("libs/auth/session.rs"), "libs/auth");
+    }
+}
@@ -3,9 +3,11 @@
 //! Analyzes test and build output and suggests Codex consultation
 //! for debugging complex failures.
 
+mod test_report;
+
 use anyhow::Result;
 use hook_common::prelude::*;
-use regex::Regex;
+use test_report::parse_test_report;
 
 // Commands that run tests or builds
 const TEST_BUILD_COMMANDS: &[&str] = &[
@@ -24,27 +26,6 @@ const TEST_BUILD_COMMANDS: &[&str] = &[
     "make build",
 ];
 
-// Patterns indicating failures
-const FAILURE_PATTERNS: &[&str] = &[
-    "FAILED",
-    "ERROR",
-    r"error\[",
-    "Error:",
-    "failed",
-    "error:",
-    "AssertionError",
-    "TypeError",
-    "ValueError",
-    "AttributeError",
-    "ImportError",
-    "ModuleNotFoundError",
-    "SyntaxError",
-    "Exception",
-    "Traceback",
-    "panic:",
-    "FAIL:",
-];
-
 // Simple errors that don't need Codex
 const SIMPLE_ERRORS: &[&str] = &[
     "ModuleNotFoundError",
@@ -96,40 +77,43 @@ fn is_test_or_build_command(command: &str) -> bool {
         .any(|cmd| cmd_lower.contains(cmd))
 }
 
+/// Decide whether `output` warrants a Codex debugging suggestion, using the
+/// real failed/passed counts from a parsed [`test_report::TestReport`]
+/// rather than counting keyword occurrences. Returns a human-readable
+/// reason (with a few trimmed failure snippets attached) when it does.
 fn has_complex_failure(output: &str) -> Option<String> {
-    // Skip if it's a simple error
     for simple in SIMPLE_ERRORS {
         if output.contains(simple) {
             return None;
         }
     }
 
-    // Count failure patterns
-    let mut failure_count = 0;
-
-    for pattern in FAILURE_PATTERNS {
-        if let Ok(re) = Regex::new(&format!("(?i){}", regex::escape(pattern))) {
-            failure_count += re.find_iter(output).count();
-        }
+    let report = parse_test_report(output)?;
+    if report.failed == 0 {
+        return None;
     }
 
-    // Multiple failures suggest need for Codex
-    if failure_count >= 3 {
-        return Some(format!(
-            "Multiple failures detected ({} issues)",
-            failure_count
-        ));
-    }
+    let snippets: Vec<String> = report
+        .first_failures
+        .iter()
+        .map(|snippet| match (&snippet.name, snippet.text.is_empty()) {
+            (Some(name), false) => format!("{}: {}", name, snippet.text),
+            (Some(name), true) => name.clone(),
+            (None, _) => snippet.text.clone(),
+        })
+        .collect();
+
+    let reason = if report.failed >= 3 {
+        format!("{} tests failed", report.failed)
+    } else {
+        format!("{} test(s) failed", report.failed)
+    };
 
-    // Single failure with traceback
-    let output_lower = output.to_lowercase();
-    if failure_count >= 1
-        && (output_lower.contains("traceback") || output_lower.contains("assertion"))
-    {
-        return Some("Test failure with traceback".to_string());
+    if snippets.is_empty() {
+        Some(reason)
+    } else {
+        Some(format!("{}: {}", reason, snippets.join(" | ")))
     }
-
-    None
 }
 
 #[cfg(test)]
@@ -144,10 +128,27 @@ mod tests {
     }
 
     #[test]
-    fn test_has_complex_failure() {
-        assert!(has_complex_failure("FAILED test1\nFAILED test2\nFAILED test3").is_some());
-        assert!(has_complex_failure("Error: test failed\nTraceback...").is_some());
-        assert!(has_complex_failure("All tests passed").is_none());
+    fn test_has_complex_failure_detects_real_failures() {
+        let output = "FAILED tests/test_x.py::test_one - AssertionError: boom\n\
+             ===== 1 failed, 2 passed in 0.1s =====\n";
+        let reason = has_complex_failure(output).unwrap();
+        assert!(reason.contains("test_one"));
+        assert!(reason.contains("AssertionError"));
+    }
+
+    #[test]
+    fn test_has_complex_failure_ignores_passing_run_that_logs_error_word() {
+        let output = "test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s\nlog: error handling path exercised\n";
+        assert!(has_complex_failure(output).is_none());
+    }
+
+    #[test]
+    fn test_has_complex_failure_skips_simple_errors() {
         assert!(has_complex_failure("ModuleNotFoundError: xyz").is_none());
     }
+
+    #[test]
+    fn test_has_complex_failure_none_without_recognized_summary() {
+        assert!(has_complex_failure("All tests passed").is_none());
+    }
 }
@@ -0,0 +1,186 @@
+//! Structured parsing of test-runner output.
+//!
+//! `has_complex_failure` used to count substring/regex hits of words like
+//! "Error" or "FAILED" across the whole output, which double-counts (one
+//! failing test prints several matching words) and misfires (a passing run
+//! that merely logs the word "error" trips it). These extractors instead
+//! read each framework's own summary line for the real failed/passed
+//! counts, plus a few per-failure snippets to hand to Codex as context.
+
+use regex::Regex;
+
+/// A trimmed snippet of output associated with one failing test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureSnippet {
+    pub name: Option<String>,
+    pub text: String,
+}
+
+/// Structured summary of a single test run, as parsed from one of the
+/// supported frameworks' console output.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TestReport {
+    pub failed: usize,
+    pub passed: usize,
+    pub first_failures: Vec<FailureSnippet>,
+}
+
+impl TestReport {
+    fn is_empty(&self) -> bool {
+        self.failed == 0 && self.passed == 0
+    }
+}
+
+/// Keep enough snippets to give Codex real error material without
+/// flooding `additionalContext`.
+const MAX_SNIPPETS: usize = 3;
+
+/// Try each known framework extractor against `output`, returning the
+/// first one that recognizes a summary line.
+pub fn parse_test_report(output: &str) -> Option<TestReport> {
+    parse_cargo_test(output)
+        .or_else(|| parse_pytest(output))
+        .or_else(|| parse_jest(output))
+        .filter(|report| !report.is_empty())
+}
+
+fn parse_cargo_test(output: &str) -> Option<TestReport> {
+    let summary = Regex::new(r"test result: \w+\. (\d+) passed; (\d+) failed;").unwrap();
+    let caps = summary.captures(output)?;
+    let passed = caps[1].parse().unwrap_or(0);
+    let failed = caps[2].parse().unwrap_or(0);
+
+    let block = Regex::new(r"(?s)---- (\S+) stdout ----\n(.*?)(?:\n\n|\z)").unwrap();
+    let first_failures = block
+        .captures_iter(output)
+        .take(MAX_SNIPPETS)
+        .map(|caps| FailureSnippet {
+            name: Some(caps[1].to_string()),
+            text: caps[2].trim().to_string(),
+        })
+        .collect();
+
+    Some(TestReport { failed, passed, first_failures })
+}
+
+fn parse_pytest(output: &str) -> Option<TestReport> {
+    let with_passed = Regex::new(r"=+ (\d+) failed, (\d+) passed.*? in ").unwrap();
+    let failed_only = Regex::new(r"=+ (\d+) failed.*? in ").unwrap();
+
+    let (failed, passed) = if let Some(caps) = with_passed.captures(output) {
+        (caps[1].parse().unwrap_or(0), caps[2].parse().unwrap_or(0))
+    } else if let Some(caps) = failed_only.captures(output) {
+        (caps[1].parse().unwrap_or(0), 0)
+    } else {
+        return None;
+    };
+
+    let failed_line = Regex::new(r"(?m)^FAILED (\S+)(?: - (.*))?$").unwrap();
+    let first_failures = failed_line
+        .captures_iter(output)
+        .take(MAX_SNIPPETS)
+        .map(|caps| FailureSnippet {
+            name: Some(caps[1].to_string()),
+            text: caps.get(2).map(|m| m.as_str().trim().to_string()).unwrap_or_default(),
+        })
+        .collect();
+
+    Some(TestReport { failed, passed, first_failures })
+}
+
+fn parse_jest(output: &str) -> Option<TestReport> {
+    let summary = Regex::new(r"Tests:\s*(\d+) failed, (\d+) passed").unwrap();
+    let caps = summary.captures(output)?;
+    let failed = caps[1].parse().unwrap_or(0);
+    let passed = caps[2].parse().unwrap_or(0);
+
+    let fail_header = Regex::new(r"(?m)^\s*(?:✕|✗|×)\s+(.+)$").unwrap();
+    let first_failures = fail_header
+        .captures_iter(output)
+        .take(MAX_SNIPPETS)
+        .map(|caps| FailureSnippet { name: Some(caps[1].trim().to_string()), text: String::new() })
+        .collect();
+
+    Some(TestReport { failed, passed, first_failures })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_OUTPUT: &str = "running 3 tests
+test foo::test_a ... ok
+test foo::test_b ... FAILED
+test foo::test_c ... FAILED
+
+failures:
+
+---- foo::test_b stdout ----
+thread 'foo::test_b' panicked at src/lib.rs:10:5:
+assertion failed: `(left == right)`
+
+---- foo::test_c stdout ----
+thread 'foo::test_c' panicked at src/lib.rs:20:5:
+index out of bounds
+
+failures:
+    foo::test_b
+    foo::test_c
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+
+    const PYTEST_OUTPUT: &str = "FAILED tests/test_x.py::test_one - AssertionError: boom
+FAILED tests/test_x.py::test_two - ValueError: bad
+===== 2 failed, 3 passed in 0.42s =====
+";
+
+    const JEST_OUTPUT: &str = "  ✕ adds numbers
+  ✕ subtracts numbers
+
+Tests:       2 failed, 3 passed, 5 total
+";
+
+    #[test]
+    fn test_parse_cargo_test() {
+        let report = parse_test_report(CARGO_OUTPUT).unwrap();
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.first_failures.len(), 2);
+        assert_eq!(report.first_failures[0].name.as_deref(), Some("foo::test_b"));
+        assert!(report.first_failures[0].text.contains("assertion failed"));
+    }
+
+    #[test]
+    fn test_parse_pytest() {
+        let report = parse_test_report(PYTEST_OUTPUT).unwrap();
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.passed, 3);
+        assert_eq!(report.first_failures.len(), 2);
+        assert_eq!(report.first_failures[0].name.as_deref(), Some("tests/test_x.py::test_one"));
+        assert!(report.first_failures[0].text.contains("AssertionError"));
+    }
+
+    #[test]
+    fn test_parse_jest() {
+        let report = parse_test_report(JEST_OUTPUT).unwrap();
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.passed, 3);
+        assert_eq!(report.first_failures.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_test_report_no_match_is_none() {
+        assert!(parse_test_report("All good, nothing to see").is_none());
+    }
+
+    #[test]
+    fn test_parse_test_report_ignores_passing_run_that_logs_error_word() {
+        // A passing cargo run whose test happens to print "error" in a log
+        // line shouldn't be mistaken for a failure report.
+        let output = "test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s\nlog: error handling path exercised\n";
+        let report = parse_test_report(output).unwrap();
+        assert_eq!(report.failed, 0);
+        assert_eq!(report.passed, 5);
+    }
+}
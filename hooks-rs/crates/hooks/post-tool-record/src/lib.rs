@@ -0,0 +1,155 @@
+//! Core logic for the memory-record hook, factored out of `main` so it
+//! can be driven by recorded fixtures in the `hook-replay` harness instead
+//! of only by a real stdin pipe.
+//!
+//! This hook never emits a `HookOutput` — it only records an observation
+//! to memory as a side effect — so `run` always returns `None`; fixtures
+//! exercising it only assert that it runs without failing.
+
+use hook_common::prelude::*;
+use hook_memory::{AgentType, MemoryEvent, MemoryScope, MemoryStorage, MemoryType};
+
+// Tools to skip regardless of the registry (default; overridable via
+// `[record] skip_tools` in `~/.config/ai/hooks.toml`, see `hook_common::config`)
+const SKIP_TOOLS: &[&str] = &["Read", "Glob", "Grep", "LS"];
+
+fn default_tool_list(defaults: &[&str]) -> Vec<String> {
+    defaults.iter().map(|s| s.to_string()).collect()
+}
+
+/// Record `input`'s tool execution to memory as a side effect. Always
+/// returns `None` (this hook never emits output).
+pub fn run(input: &HookInput) -> Option<HookOutput> {
+    let config = HooksConfig::load();
+    let registry = ToolHandlerRegistry::default();
+    let tool_name = input.tool_name.as_str();
+
+    let recordable_tools =
+        config.record.recordable_tools.clone().unwrap_or_else(|| registry.record_tools());
+    let skip_tools =
+        config.record.skip_tools.clone().unwrap_or_else(|| default_tool_list(SKIP_TOOLS));
+
+    if skip_tools.iter().any(|t| t == tool_name) || !recordable_tools.iter().any(|t| t == tool_name) {
+        return None;
+    }
+
+    let tool_output = input.tool_output.as_deref().unwrap_or("");
+    record_tool_result(
+        &registry,
+        tool_name,
+        &input.tool_input,
+        tool_output,
+        &config.record.extra_failure_indicators,
+    );
+
+    None
+}
+
+fn record_tool_result(
+    registry: &ToolHandlerRegistry,
+    tool_name: &str,
+    tool_input: &hook_common::input::ToolInput,
+    tool_output: &str,
+    extra_failure_indicators: &[String],
+) -> bool {
+    // Use global memory path (default: ~/.config/ai/memory/events.jsonl)
+    let storage_path = match MemoryStorage::default_path() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Warning: Failed to determine memory storage path: {}", e);
+            return false;
+        }
+    };
+    let storage = MemoryStorage::new(storage_path);
+
+    // Extract summary
+    let summary = registry
+        .get(tool_name)
+        .and_then(|handler| handler.summary(tool_input, tool_output))
+        .unwrap_or_else(|| format!("{} execution", tool_name));
+    let success = determine_success(tool_output, extra_failure_indicators);
+
+    // Build content
+    let content = if success {
+        format!("Tool: {}\n{}", tool_name, summary)
+    } else {
+        let error_preview = truncate_content(tool_output, 200);
+        format!("[FAILURE] Tool: {}\n{}\nError: {}", tool_name, summary, error_preview)
+    };
+
+    let mut event = MemoryEvent::new(
+        content,
+        MemoryType::Observation,
+        MemoryScope::Session,
+        AgentType::Claude,
+    );
+    event.context = format!("tool:{}", tool_name);
+
+    storage.append(&event).is_ok()
+}
+
+/// Built-in failure indicators plus any `extra_indicators` supplied via
+/// `[record] extra_failure_indicators` in `~/.config/ai/hooks.toml`.
+fn determine_success(tool_output: &str, extra_indicators: &[String]) -> bool {
+    let output_lower = tool_output.to_lowercase();
+
+    let failure_indicators = [
+        "error:",
+        "failed",
+        "exception",
+        "traceback",
+        "permission denied",
+        "not found",
+        "command not found",
+    ];
+
+    for indicator in failure_indicators {
+        if output_lower.contains(indicator) {
+            return false;
+        }
+    }
+
+    for indicator in extra_indicators {
+        if output_lower.contains(&indicator.to_lowercase()) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn truncate_content(content: &str, max_length: usize) -> String {
+    if content.len() <= max_length {
+        content.to_string()
+    } else {
+        format!("{}...", &content[..max_length.saturating_sub(3)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_determine_success() {
+        assert!(determine_success("All tests passed", &[]));
+        assert!(!determine_success("Error: something failed", &[]));
+        assert!(!determine_success("Traceback (most recent call last):", &[]));
+    }
+
+    #[test]
+    fn test_determine_success_extra_indicators() {
+        let extra = vec!["deprecationwarning".to_string()];
+        assert!(!determine_success("DeprecationWarning: foo is deprecated", &extra));
+        assert!(determine_success("DeprecationWarning: foo is deprecated", &[]));
+    }
+
+    #[test]
+    fn test_truncate_content() {
+        assert_eq!(truncate_content("short", 100), "short");
+        let long = "a".repeat(200);
+        let truncated = truncate_content(&long, 100);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.len() <= 100);
+    }
+}
@@ -0,0 +1,155 @@
+//! Core decision logic for the memory-recall hook, factored out of `main`
+//! so it can be driven by recorded fixtures in the `hook-replay` harness
+//! instead of only by a real stdin pipe.
+
+use hook_common::prelude::*;
+use hook_memory::MemoryStorage;
+
+// Maximum memories to inject (default; overridable via `[recall] max_recall`)
+const MAX_RECALL: usize = 5;
+
+/// Decide whether `input` should have memories injected. `None` means
+/// silent pass.
+pub fn run(input: &HookInput) -> Option<HookOutput> {
+    let config = HooksConfig::load();
+    let registry = ToolHandlerRegistry::default();
+    let tool_name = input.tool_name.as_str();
+
+    let recall_tools = config.recall.tools.clone().unwrap_or_else(|| registry.recall_tools());
+    if !recall_tools.iter().any(|t| t == tool_name) {
+        return None;
+    }
+
+    let max_recall = config.recall.max_recall.unwrap_or(MAX_RECALL);
+    let query = registry
+        .get(tool_name)
+        .and_then(|handler| handler.query(&input.tool_input))
+        .unwrap_or_else(|| tool_name.to_string());
+    let memories = recall_memories(&query, max_recall);
+
+    if memories.is_empty() {
+        return None;
+    }
+
+    let context = format_memories_for_context(&memories);
+    Some(HookOutput::allow().with_context(context))
+}
+
+struct RecalledMemory {
+    content: String,
+    memory_type: String,
+    score: f32,
+}
+
+fn recall_memories(query: &str, max_recall: usize) -> Vec<RecalledMemory> {
+    // Use global memory path (default: ~/.config/ai/memory/events.jsonl)
+    let storage_path = match MemoryStorage::default_path() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Warning: Failed to determine memory storage path: {}", e);
+            return Vec::new();
+        }
+    };
+    let storage = MemoryStorage::new(storage_path);
+
+    // Relevance-rank across all stored memories (see
+    // `hook_memory::rank`: BM25 + recency + confidence + tag match).
+    let ranked = match storage.query(query, max_recall) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+
+    ranked
+        .into_iter()
+        .map(|ranked| RecalledMemory {
+            content: ranked.event.content,
+            memory_type: format!("{:?}", ranked.event.memory_type),
+            score: ranked.score as f32,
+        })
+        .collect()
+}
+
+fn format_memories_for_context(memories: &[RecalledMemory]) -> String {
+    if memories.is_empty() {
+        return String::new();
+    }
+
+    let mut lines = vec!["# 関連する記憶\n".to_string()];
+
+    for (i, m) in memories.iter().enumerate() {
+        let content = if m.content.len() > 150 {
+            format!("{}...", &m.content[..147])
+        } else {
+            m.content.clone()
+        };
+
+        lines.push(format!(
+            "{}. [{}] {} (関連度: {:.2})",
+            i + 1,
+            m.memory_type,
+            content,
+            m.score
+        ));
+    }
+
+    lines.push("\n---\n".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hook_memory::{AgentType, MemoryScope, MemoryType};
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    // `recall_memories` resolves its storage path from the process-global
+    // `AI_MEMORY_PATH` env var, so tests that set it must not run concurrently.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_recall_tools() {
+        let tools = ToolHandlerRegistry::default().recall_tools();
+        assert!(tools.iter().any(|t| t == "Bash"));
+        assert!(tools.iter().any(|t| t == "Edit"));
+    }
+
+    #[test]
+    fn test_recall_memories_ranks_via_storage_query() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let path_str = path.to_string_lossy().to_string();
+        unsafe {
+            std::env::set_var("AI_MEMORY_PATH", &path_str);
+        }
+
+        let storage = MemoryStorage::new(path_str);
+        storage
+            .append(&hook_memory::MemoryEvent::new(
+                "rust clippy lint failures",
+                MemoryType::Observation,
+                MemoryScope::User,
+                AgentType::System,
+            ))
+            .unwrap();
+        storage
+            .append(&hook_memory::MemoryEvent::new(
+                "unrelated content here",
+                MemoryType::Observation,
+                MemoryScope::User,
+                AgentType::System,
+            ))
+            .unwrap();
+
+        let recalled = recall_memories("clippy lint", MAX_RECALL);
+
+        unsafe {
+            std::env::remove_var("AI_MEMORY_PATH");
+        }
+
+        assert_eq!(recalled.len(), 2);
+        assert!(recalled[0].content.contains("clippy"));
+        assert!(recalled[0].score > recalled[1].score);
+    }
+}